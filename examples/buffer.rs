@@ -0,0 +1,71 @@
+//! A growable byte buffer on top of [`Allocation`], similar to a `Vec<u8>`.
+//!
+//! Run with
+//!
+//! ```bash
+//! cargo run --example buffer
+//! ```
+
+use untyped_box::Allocation;
+
+mod buffer {
+    use super::Allocation;
+
+    /// A byte buffer tracking how many bytes at the start of its allocation are initialized.
+    pub struct Buffer {
+        alloc: Allocation,
+        len: usize,
+    }
+
+    impl Buffer {
+        pub fn with_capacity(cap: usize) -> Self {
+            Self {
+                alloc: Allocation::with_capacity_bytes(cap),
+                len: 0,
+            }
+        }
+        pub fn capacity(&self) -> usize {
+            self.alloc.layout().size()
+        }
+        pub fn push(&mut self, byte: u8) {
+            self.extend_from_slice(&[byte]);
+        }
+        pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+            // `reserve` counts from the requested size, which never lags behind the length.
+            let additional = (self.len + bytes.len()).saturating_sub(self.alloc.requested_size());
+            self.alloc.reserve(additional);
+            let dst = &mut self.alloc.as_bytes_mut()[self.len..][..bytes.len()];
+            for (dst, &src) in dst.iter_mut().zip(bytes) {
+                dst.write(src);
+            }
+            self.len += bytes.len();
+        }
+        pub fn as_slice(&self) -> &[u8] {
+            // SAFETY: the first `len` bytes have been written by `extend_from_slice`.
+            unsafe { core::slice::from_raw_parts(self.alloc.as_ptr::<u8>().as_ptr(), self.len) }
+        }
+    }
+}
+
+fn main() {
+    let mut buf = buffer::Buffer::with_capacity(4);
+    let mut reallocations = 0;
+    let mut capacity = buf.capacity();
+    for i in 0..1000u32 {
+        buf.push(i as u8);
+        if buf.capacity() != capacity {
+            reallocations += 1;
+            capacity = buf.capacity();
+        }
+    }
+    buf.extend_from_slice(b"done");
+    assert_eq!(buf.as_slice().len(), 1004);
+    assert!(buf.as_slice()[..1000]
+        .iter()
+        .enumerate()
+        .all(|(i, &b)| b == i as u8));
+    println!(
+        "pushed {} bytes with {reallocations} reallocations",
+        buf.as_slice().len()
+    );
+}
@@ -1 +1,9 @@
+// The allocator trait is provided by allocator-api2, re-exporting the unstable std trait with the nightly feature.
+// Both already implement `Allocator` for `&A`, so allocators can be shared by reference, e.g. with
+// `Allocation::new_in(layout, &arena)`.
 pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+
+// Methods handling allocation failure by calling this are compiled out with `--cfg no_global_oom_handling`, like in
+// `alloc`. Importing it from here lets the compiler point out methods that are missing the cfg.
+#[cfg(not(no_global_oom_handling))]
+pub(crate) use alloc::alloc::handle_alloc_error;
@@ -0,0 +1,196 @@
+use core::{
+    alloc::Layout,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::alloc_shim::{AllocError, Allocator};
+
+/// An allocator wrapper that records statistics about the memory allocated through it.
+///
+/// All calls are forwarded to the wrapped allocator. The wrapper tracks the number of bytes
+/// currently allocated and the number of live allocations.
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly-std-conversions", feature(allocator_api))]
+/// # use core::alloc::Layout;
+/// # use allocator_api2::alloc::Global;
+/// # use untyped_box::{Allocation, TrackingAllocator};
+/// let tracking = TrackingAllocator::new(Global);
+/// let alloc = Allocation::new_in(Layout::new::<[u8; 16]>(), &tracking);
+/// assert_eq!(tracking.bytes_allocated(), 16);
+/// assert_eq!(tracking.live_allocations(), 1);
+/// drop(alloc);
+/// assert_eq!(tracking.live_allocations(), 0);
+/// ```
+///
+/// Bytes are counted by the requested layouts. To keep the count exact, blocks are handed out with exactly the
+/// requested size, even if the wrapped allocator provides more memory.
+#[derive(Debug, Default)]
+pub struct TrackingAllocator<A> {
+    inner: A,
+    bytes: AtomicUsize,
+    live: AtomicUsize,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Wrap an allocator, starting with all counters at zero.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            bytes: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+        }
+    }
+    /// The number of bytes currently allocated through this allocator.
+    pub fn bytes_allocated(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+    /// The number of allocations made through this allocator that have not been deallocated.
+    pub fn live_allocations(&self) -> usize {
+        self.live.load(Ordering::Relaxed)
+    }
+    /// Get a reference to the wrapped allocator.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+    /// Unwrap the wrapped allocator, discarding the statistics.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    // Callers may deallocate with any size between the requested and the returned size. Returning only the requested
+    // size makes sure the same size is subtracted later.
+    fn track_allocate(&self, layout: Layout, block: NonNull<[u8]>) -> NonNull<[u8]> {
+        self.bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        self.live.fetch_add(1, Ordering::Relaxed);
+        NonNull::slice_from_raw_parts(block.cast(), layout.size())
+    }
+    fn track_realloc(
+        &self,
+        old_layout: Layout,
+        new_layout: Layout,
+        block: NonNull<[u8]>,
+    ) -> NonNull<[u8]> {
+        self.bytes.fetch_add(new_layout.size(), Ordering::Relaxed);
+        self.bytes.fetch_sub(old_layout.size(), Ordering::Relaxed);
+        NonNull::slice_from_raw_parts(block.cast(), new_layout.size())
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for TrackingAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner
+            .allocate(layout)
+            .map(|b| self.track_allocate(layout, b))
+    }
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner
+            .allocate_zeroed(layout)
+            .map(|b| self.track_allocate(layout, b))
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.inner.deallocate(ptr, layout) };
+        self.bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+        self.live.fetch_sub(1, Ordering::Relaxed);
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.inner.grow(ptr, old_layout, new_layout) }
+            .map(|b| self.track_realloc(old_layout, new_layout, b))
+    }
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.inner.grow_zeroed(ptr, old_layout, new_layout) }
+            .map(|b| self.track_realloc(old_layout, new_layout, b))
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.inner.shrink(ptr, old_layout, new_layout) }
+            .map(|b| self.track_realloc(old_layout, new_layout, b))
+    }
+}
+
+/// An allocator wrapper that aligns every allocation to at least a cache line of 64 bytes.
+///
+/// This avoids false sharing between allocations that are accessed from different threads. Layouts are padded
+/// before forwarding to the wrapped allocator, and the same padded layout is reconstructed when deallocating.
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly-std-conversions", feature(allocator_api))]
+/// # use core::alloc::Layout;
+/// # use allocator_api2::alloc::Global;
+/// # use untyped_box::{Allocation, CacheAligned};
+/// let alloc = Allocation::new_in(Layout::new::<u8>(), CacheAligned(Global));
+/// assert_eq!(alloc.as_ptr::<u8>().as_ptr() as usize % CacheAligned::<Global>::ALIGN, 0);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheAligned<A>(pub A);
+
+impl<A> CacheAligned<A> {
+    /// The minimum alignment of every allocation.
+    pub const ALIGN: usize = 64;
+
+    fn padded(layout: Layout) -> Result<Layout, AllocError> {
+        layout.align_to(Self::ALIGN).map_err(|_| AllocError)
+    }
+    // Layouts that were allocated before have been padded successfully, so this can not fail.
+    unsafe fn padded_unchecked(layout: Layout) -> Layout {
+        let align = layout.align().max(Self::ALIGN);
+        unsafe { Layout::from_size_align_unchecked(layout.size(), align) }
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for CacheAligned<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate(Self::padded(layout)?)
+    }
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate_zeroed(Self::padded(layout)?)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.0.deallocate(ptr, Self::padded_unchecked(layout)) }
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = unsafe { Self::padded_unchecked(old_layout) };
+        unsafe { self.0.grow(ptr, old_layout, Self::padded(new_layout)?) }
+    }
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = unsafe { Self::padded_unchecked(old_layout) };
+        unsafe {
+            self.0
+                .grow_zeroed(ptr, old_layout, Self::padded(new_layout)?)
+        }
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = unsafe { Self::padded_unchecked(old_layout) };
+        unsafe { self.0.shrink(ptr, old_layout, Self::padded(new_layout)?) }
+    }
+}
@@ -0,0 +1,63 @@
+use core::mem::MaybeUninit;
+
+use allocator_api2::{boxed::Box, vec::Vec};
+
+use crate::{
+    alloc_shim::Allocator,
+    std_conversions::{check_box_layout, check_vec_layout},
+    Allocation, BoxConversionError, VecConversionError,
+};
+
+/// Conversions to the `Box` and `Vec` types of `allocator-api2`, supporting any allocator on stable.
+///
+/// With the `nightly-std-conversions` feature, these are the same types as in std.
+impl<A: Allocator> Allocation<A> {
+    /// Convert the allocation into an [`allocator_api2::boxed::Box`].
+    ///
+    /// See [`try_into_box`](Self::try_into_box) for more details.
+    pub fn try_into_api2_box<T>(self) -> Result<Box<MaybeUninit<T>, A>, BoxConversionError> {
+        let () = check_box_layout::<_, T>(&self)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        Ok(unsafe { Box::from_raw_in(ptr.as_ptr().cast(), alloc) })
+    }
+    /// Convert the allocation into an [`allocator_api2::vec::Vec`].
+    ///
+    /// See [`try_into_vec`](Self::try_into_vec) for more details.
+    pub fn try_into_api2_vec<T>(self) -> Result<Vec<T, A>, VecConversionError> {
+        let capacity = check_vec_layout::<_, T>(&self)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        Ok(unsafe { Vec::from_raw_parts_in(ptr.as_ptr().cast(), 0, capacity, alloc) })
+    }
+}
+
+// With the nightly feature, these are covered by the conversions from std types.
+#[cfg(not(feature = "nightly-std-conversions"))]
+mod from_api2 {
+    use core::{alloc::Layout, ptr::NonNull};
+
+    use allocator_api2::{boxed::Box, vec::Vec};
+
+    use crate::{alloc_shim::Allocator, Allocation};
+
+    /// The value in the box will not be dropped, as if passed to [`forget`](core::mem::forget).
+    impl<T: ?Sized, A: Allocator> From<Box<T, A>> for Allocation<A> {
+        fn from(value: Box<T, A>) -> Self {
+            let layout = Layout::for_value(&*value);
+            let (ptr, alloc) = Box::into_raw_with_allocator(value);
+            let ptr = unsafe { NonNull::new_unchecked(ptr) };
+            unsafe { Self::from_parts_in(ptr.cast(), layout, alloc) }
+        }
+    }
+
+    /// The values in the `Vec` will not be dropped, as if by a call to [`vec.set_len(0)`](Vec::set_len).
+    impl<T, A: Allocator> From<Vec<T, A>> for Allocation<A> {
+        fn from(value: Vec<T, A>) -> Self {
+            let mut value = value;
+            unsafe { value.set_len(0) };
+            let layout = Layout::for_value(value.spare_capacity_mut());
+            let (ptr, _, _, alloc) = value.into_raw_parts_with_alloc();
+            let ptr = unsafe { NonNull::new_unchecked(ptr) };
+            unsafe { Self::from_parts_in(ptr.cast(), layout, alloc) }
+        }
+    }
+}
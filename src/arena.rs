@@ -0,0 +1,121 @@
+use core::{alloc::Layout, cell::Cell, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use crate::alloc_shim::{AllocError, Allocator};
+
+/// A bump allocator handing out memory from a caller-provided buffer.
+///
+/// Allocations are placed one after another in the buffer, respecting their alignment. When the buffer is
+/// exhausted, allocation fails with an [`AllocError`]. Deallocating is a no-op, the memory is only reclaimed
+/// once the buffer is released. Growing and shrinking only succeeds for the most recent allocation.
+///
+/// This allows to allocate without a global heap, e.g. in `no_std` environments:
+///
+/// ```
+/// # use core::{alloc::Layout, mem::MaybeUninit};
+/// # use untyped_box::{Allocation, Bump};
+/// let mut buffer = [MaybeUninit::uninit(); 64];
+/// let bump = Bump::new(&mut buffer);
+/// let alloc = Allocation::new_in(Layout::new::<u32>(), &bump);
+/// assert_eq!(bump.used(), 4);
+/// ```
+pub struct Bump<'a> {
+    start: NonNull<u8>,
+    len: usize,
+    offset: Cell<usize>,
+    // Offset of the most recent allocation
+    last: Cell<usize>,
+    _buffer: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+impl<'a> Bump<'a> {
+    /// Create a bump allocator handing out memory from the given buffer.
+    pub fn new(buffer: &'a mut [MaybeUninit<u8>]) -> Self {
+        let len = buffer.len();
+        let start = NonNull::from(buffer).cast();
+        Self {
+            start,
+            len,
+            offset: Cell::new(0),
+            last: Cell::new(0),
+            _buffer: PhantomData,
+        }
+    }
+    /// The number of bytes used in the buffer, including padding for alignment.
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    fn block(&self, offset: usize, size: usize) -> NonNull<[u8]> {
+        // SAFETY: callers ensure that `offset + size` is in bounds of the buffer
+        let ptr = unsafe { self.start.add(offset) };
+        NonNull::slice_from_raw_parts(ptr, size)
+    }
+    fn offset_of(&self, ptr: NonNull<u8>) -> usize {
+        ptr.as_ptr().addr() - self.start.as_ptr().addr()
+    }
+    fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let offset = self.offset_of(ptr);
+        let is_last = offset == self.last.get() && offset + old_layout.size() == self.offset.get();
+        #[allow(clippy::manual_is_multiple_of)] // would require MSRV of 1.87
+        if !is_last || ptr.as_ptr().addr() % new_layout.align() != 0 {
+            return Err(AllocError);
+        }
+        let end = offset.checked_add(new_layout.size()).ok_or(AllocError)?;
+        if end > self.len {
+            return Err(AllocError);
+        }
+        self.offset.set(end);
+        Ok(self.block(offset, new_layout.size()))
+    }
+}
+
+unsafe impl Allocator for Bump<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.start.as_ptr().addr();
+        let next = base + self.offset.get();
+        let aligned = next
+            .checked_next_multiple_of(layout.align())
+            .ok_or(AllocError)?
+            - base;
+        let end = aligned.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > self.len {
+            return Err(AllocError);
+        }
+        self.last.set(aligned);
+        self.offset.set(end);
+        Ok(self.block(aligned, layout.size()))
+    }
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.resize(ptr, old_layout, new_layout)
+    }
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.resize(ptr, old_layout, new_layout)?;
+        let tail = new_layout.size() - old_layout.size();
+        unsafe { ptr.add(old_layout.size()).write_bytes(0, tail) };
+        Ok(block)
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.resize(ptr, old_layout, new_layout)
+    }
+}
@@ -0,0 +1,70 @@
+use core::alloc::{Layout, LayoutError};
+
+use crate::{
+    alloc_shim::{AllocError, Allocator},
+    Allocation,
+};
+
+/// A guard batching several resize requests of an [`Allocation`] into a single reallocation.
+///
+/// Created by [`Allocation::begin_batch`]. The guard accumulates the largest requested size and the
+/// strictest requested alignment across calls to [`request`](Self::request). The allocation is
+/// reallocated at most once, either by calling [`commit`](Self::commit) or when the guard is dropped.
+///
+/// Batching never shrinks the allocation.
+pub struct BatchResize<'a, A: Allocator> {
+    allocation: &'a mut Allocation<A>,
+    pending: Layout,
+}
+
+impl<'a, A: Allocator> BatchResize<'a, A> {
+    pub(crate) fn new(allocation: &'a mut Allocation<A>) -> Self {
+        let pending = allocation.layout();
+        Self {
+            allocation,
+            pending,
+        }
+    }
+    /// Request that the allocation fits at least the given layout after the batch is committed.
+    ///
+    /// Returns an error if the accumulated size, rounded up to the accumulated alignment, overflows.
+    /// In this case, the pending layout is unchanged.
+    pub fn request(&mut self, layout: Layout) -> Result<(), LayoutError> {
+        let size = self.pending.size().max(layout.size());
+        let align = self.pending.align().max(layout.align());
+        self.pending = Layout::from_size_align(size, align)?;
+        Ok(())
+    }
+    /// The layout the allocation will be reallocated to when the batch is committed.
+    pub fn pending(&self) -> Layout {
+        self.pending
+    }
+    /// Perform the batched reallocation.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, the allocation is unchanged.
+    /// Dropping the guard instead calls [`alloc::alloc::handle_alloc_error`] on failure, or leaves the allocation
+    /// unchanged when compiled with `--cfg no_global_oom_handling`.
+    pub fn commit(mut self) -> Result<(), AllocError> {
+        let result = self.apply();
+        core::mem::forget(self);
+        result
+    }
+    fn apply(&mut self) -> Result<(), AllocError> {
+        if self.pending == self.allocation.layout() {
+            return Ok(());
+        }
+        self.allocation.try_realloc(self.pending)
+    }
+}
+
+impl<A: Allocator> Drop for BatchResize<'_, A> {
+    fn drop(&mut self) {
+        let result = self.apply();
+        #[cfg(not(no_global_oom_handling))]
+        let () =
+            result.unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(self.pending));
+        // Without global OOM handling, a failed reallocation leaves the allocation unchanged. Use `commit` to observe it.
+        #[cfg(no_global_oom_handling)]
+        let _ = result;
+    }
+}
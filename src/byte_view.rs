@@ -0,0 +1,72 @@
+use core::{
+    borrow::Borrow,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{
+    alloc_shim::{Allocator, Global},
+    Allocation,
+};
+
+/// An allocation whose bytes are all initialized, compared and hashed by its contents.
+///
+/// Since [`MaybeUninit<u8>`](core::mem::MaybeUninit) implements neither [`Hash`] nor [`Eq`], an [`Allocation`] can not
+/// be used as the key of a map directly. This wrapper asserts that all bytes are initialized once, on construction,
+/// and then behaves like a `[u8]`. It borrows as `[u8]`, so maps keyed by it can be queried with a byte slice.
+///
+/// ```
+/// # use std::collections::HashSet;
+/// # use untyped_box::{Allocation, ByteView};
+/// let key = unsafe { ByteView::new(Allocation::from_slice(b"key")) };
+/// let set = HashSet::from([key]);
+/// assert!(set.contains(&b"key"[..]));
+/// ```
+pub struct ByteView<A: Allocator = Global> {
+    inner: Allocation<A>,
+}
+
+impl<A: Allocator> ByteView<A> {
+    /// View the bytes of an allocation.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the allocation must be initialized, see [`Allocation::as_init_bytes`].
+    pub unsafe fn new(inner: Allocation<A>) -> Self {
+        Self { inner }
+    }
+    /// Get the underlying allocation.
+    pub fn into_inner(self) -> Allocation<A> {
+        self.inner
+    }
+}
+
+impl<A: Allocator> Deref for ByteView<A> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: asserted on construction. The wrapper does not allow writing uninitialized bytes or reallocating.
+        unsafe { self.inner.as_init_bytes() }
+    }
+}
+
+impl<A: Allocator> Borrow<[u8]> for ByteView<A> {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<A: Allocator, B: Allocator> PartialEq<ByteView<B>> for ByteView<A> {
+    fn eq(&self, other: &ByteView<B>) -> bool {
+        **self == **other
+    }
+}
+
+impl<A: Allocator> Eq for ByteView<A> {}
+
+// Must agree with the hash of `[u8]`, since the view borrows as a byte slice.
+impl<A: Allocator> Hash for ByteView<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
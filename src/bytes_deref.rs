@@ -0,0 +1,52 @@
+use core::{
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    alloc_shim::{Allocator, Global},
+    Allocation,
+};
+
+/// An allocation that dereferences to its possibly uninitialized bytes.
+///
+/// The bytes live in the memory block of the allocation, hence the address of the dereferenced slice does not change
+/// when the wrapper is moved. With the `stable_deref` feature, this implements `StableDeref`, which allows using it
+/// with libraries building self-referential structures.
+///
+/// `CloneStableDeref` is not implemented, since there is no clone sharing the same memory.
+pub struct AllocationBytes<A: Allocator = Global> {
+    inner: Allocation<A>,
+}
+
+impl<A: Allocator> AllocationBytes<A> {
+    /// Get the underlying allocation.
+    pub fn into_inner(self) -> Allocation<A> {
+        self.inner
+    }
+}
+
+impl<A: Allocator> From<Allocation<A>> for AllocationBytes<A> {
+    fn from(inner: Allocation<A>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<A: Allocator> Deref for AllocationBytes<A> {
+    type Target = [MaybeUninit<u8>];
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_bytes()
+    }
+}
+
+impl<A: Allocator> DerefMut for AllocationBytes<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.as_bytes_mut()
+    }
+}
+
+// SAFETY: The slice points into the memory block owned by the allocation, which does not move with the wrapper.
+// The wrapper does not allow reallocating the memory.
+#[cfg(feature = "stable_deref")]
+unsafe impl<A: Allocator> stable_deref_trait::StableDeref for AllocationBytes<A> {}
@@ -0,0 +1,37 @@
+use alloc::vec::Vec;
+
+use bytes::BytesMut;
+
+use crate::Allocation;
+
+/// Conversions to and from [`BytesMut`] of the `bytes` crate.
+impl Allocation {
+    /// Convert the allocation into an empty [`BytesMut`].
+    ///
+    /// The capacity of the returned buffer is `self.layout().size()`. The memory is reused without copying, but
+    /// reallocated to an alignment of `1` if necessary, see [`into_vec_fixup`](Self::into_vec_fixup).
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_bytes_mut(self) -> BytesMut {
+        let Ok(vec) = self.into_vec_fixup::<u8>() else {
+            unreachable!("u8 is not zero-sized")
+        };
+        if vec.capacity() == 0 {
+            return BytesMut::new();
+        }
+        // A freshly converted vec is uniquely owned, which makes the conversion to `BytesMut` zero-copy
+        bytes::Bytes::from(vec)
+            .try_into_mut()
+            .unwrap_or_else(|_| unreachable!("buffer is uniquely owned"))
+    }
+    /// Convert a [`BytesMut`] into an allocation.
+    ///
+    /// This is zero-copy if `bytes` uniquely owns its storage and has not been advanced. Otherwise, the contents are
+    /// copied into a new buffer. The contents of `bytes` are forgotten, as with `Allocation as From<Vec<u8>>`.
+    pub fn from_bytes_mut(bytes: BytesMut) -> Self {
+        Self::from(Vec::<u8>::from(bytes))
+    }
+}
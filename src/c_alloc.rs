@@ -0,0 +1,50 @@
+// Helpers shared by the allocators on top of C-style `malloc`, `realloc` and `free` functions.
+use core::{alloc::Layout, ffi::c_void, ptr::NonNull};
+
+use crate::alloc_shim::{AllocError, Allocator};
+
+// Mirrors the condition of `std::alloc::System`: a `malloc`-like function returns memory aligned for any object that
+// fits into the requested size, which is less than its fundamental alignment `max_align` for small sizes.
+pub(crate) fn malloc_aligns(layout: Layout, max_align: usize) -> bool {
+    layout.align() <= max_align && layout.align() <= layout.size()
+}
+
+pub(crate) fn dangling(layout: Layout) -> NonNull<[u8]> {
+    let ptr = core::ptr::without_provenance_mut::<u8>(layout.align());
+    let ptr = core::ptr::slice_from_raw_parts_mut(ptr, 0);
+    unsafe { NonNull::new_unchecked(ptr) }
+}
+
+pub(crate) fn to_slice(ptr: *mut c_void, size: usize) -> Result<NonNull<[u8]>, AllocError> {
+    let ptr = core::ptr::slice_from_raw_parts_mut(ptr.cast::<u8>(), size);
+    NonNull::new(ptr).ok_or(AllocError)
+}
+
+// Implements `grow` and `shrink` with a `realloc`-like function. Zero-sized blocks are never passed to `realloc`, and
+// since `realloc` does not preserve a stricter alignment than `malloc_aligns` allows, such blocks are moved to a fresh
+// allocation of `alloc` instead.
+pub(crate) unsafe fn reallocate(
+    alloc: &impl Allocator,
+    ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_layout: Layout,
+    max_align: usize,
+    realloc: impl FnOnce(*mut c_void, usize) -> *mut c_void,
+) -> Result<NonNull<[u8]>, AllocError> {
+    if old_layout.size() == 0 {
+        return alloc.allocate(new_layout);
+    }
+    if new_layout.size() == 0 {
+        unsafe { alloc.deallocate(ptr, old_layout) };
+        return Ok(dangling(new_layout));
+    }
+    if !malloc_aligns(new_layout, max_align) {
+        let new_ptr = alloc.allocate(new_layout)?;
+        let len = old_layout.size().min(new_layout.size());
+        unsafe { ptr.copy_to_nonoverlapping(new_ptr.cast(), len) };
+        unsafe { alloc.deallocate(ptr, old_layout) };
+        return Ok(new_ptr);
+    }
+    let ptr = realloc(ptr.as_ptr().cast(), new_layout.size());
+    to_slice(ptr, new_layout.size())
+}
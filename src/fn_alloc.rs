@@ -0,0 +1,112 @@
+use core::{alloc::Layout, ffi::c_void, ptr::NonNull};
+
+use crate::{
+    alloc_shim::{AllocError, Allocator},
+    c_alloc,
+};
+
+/// Allocation callback with the signature of `malloc`.
+pub type AllocFn = unsafe extern "C" fn(size: usize) -> *mut c_void;
+/// Reallocation callback with the signature of `realloc`.
+pub type ReallocFn = unsafe extern "C" fn(ptr: *mut c_void, size: usize) -> *mut c_void;
+/// Deallocation callback with the signature of `free`.
+pub type FreeFn = unsafe extern "C" fn(ptr: *mut c_void);
+
+/// An allocator calling user-provided `malloc`, `realloc` and `free` function pointers.
+///
+/// This allows an [`Allocation`](crate::Allocation) to manage memory of a custom C allocator, for example one passed
+/// as callbacks to a library.
+///
+/// # Alignment
+///
+/// A `malloc`-like function only guarantees some fixed alignment, usually the alignment of `max_align_t`.
+/// This alignment has to be given to [`FnAllocator::new`]. For small sizes, only the alignment of objects fitting
+/// into the size is assumed. Requests for a stricter alignment, or an alignment larger than the size, fail with
+/// an [`AllocError`].
+///
+/// Zero-sized allocations do not call into the callbacks and use a dangling pointer instead.
+#[derive(Debug, Clone, Copy)]
+pub struct FnAllocator {
+    alloc: AllocFn,
+    realloc: ReallocFn,
+    free: FreeFn,
+    max_align: usize,
+}
+
+impl FnAllocator {
+    /// Create an allocator from function pointers.
+    ///
+    /// # Safety
+    ///
+    /// The functions must behave like `malloc`, `realloc` and `free` of the same C allocator. In particular:
+    /// - `alloc(size)` returns null or a pointer to `size` bytes of fresh memory, aligned to `max_align` or to the
+    ///   largest power of two not exceeding `size`, whichever is smaller.
+    /// - `realloc(ptr, size)` for a `ptr` previously returned by these functions returns null, leaving `ptr` untouched,
+    ///   or a pointer to `size` bytes, aligned as for `alloc(size)`, that preserves the contents of `ptr`.
+    /// - `free(ptr)` deallocates a pointer previously returned by `alloc` or `realloc`.
+    pub const unsafe fn new(
+        alloc: AllocFn,
+        realloc: ReallocFn,
+        free: FreeFn,
+        max_align: usize,
+    ) -> Self {
+        Self {
+            alloc,
+            realloc,
+            free,
+            max_align,
+        }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            c_alloc::reallocate(
+                self,
+                ptr,
+                old_layout,
+                new_layout,
+                self.max_align,
+                |ptr, size| (self.realloc)(ptr, size),
+            )
+        }
+    }
+}
+
+unsafe impl Allocator for FnAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(c_alloc::dangling(layout));
+        }
+        if !c_alloc::malloc_aligns(layout, self.max_align) {
+            return Err(AllocError);
+        }
+        let ptr = unsafe { (self.alloc)(layout.size()) };
+        c_alloc::to_slice(ptr, layout.size())
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            unsafe { (self.free)(ptr.as_ptr().cast()) }
+        }
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.reallocate(ptr, old_layout, new_layout) }
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.reallocate(ptr, old_layout, new_layout) }
+    }
+}
@@ -0,0 +1,37 @@
+use zerocopy::{FromBytes, Immutable};
+
+use crate::{alloc_shim::Allocator, Allocation};
+
+/// Typed views for [`zerocopy`] types.
+impl<A: Allocator> Allocation<A> {
+    /// View the start of the allocation as a `T`.
+    ///
+    /// Returns `None` if the allocation is too small, or not aligned enough to contain a `T`.
+    ///
+    /// # Safety
+    ///
+    /// The first `size_of::<T>()` bytes of the allocation must be initialized. Any initialized bit pattern is a
+    /// valid `T`, since `T` is [`FromBytes`]. Since `T` is [`Immutable`], the shared view can not be used to mutate
+    /// the allocation through interior mutability.
+    pub unsafe fn try_as_ref<T: FromBytes + Immutable>(&self) -> Option<&T> {
+        if !self.fits::<T>() {
+            return None;
+        }
+        Some(unsafe { self.as_ptr::<T>().as_ref() })
+    }
+    /// View the whole allocation as a slice of as many `T` as fit into it.
+    ///
+    /// Returns `None` if the allocation is not aligned enough to contain a `T`. Trailing bytes that do not fit a whole
+    /// `T` are not part of the slice, see [`reinterpret`](Self::reinterpret).
+    ///
+    /// # Safety
+    ///
+    /// The bytes of the allocation covered by the slice must be initialized, see [`try_as_ref`](Self::try_as_ref).
+    pub unsafe fn try_as_slice<T: FromBytes + Immutable>(&self) -> Option<&[T]> {
+        if !self.is_aligned_for::<T>() {
+            return None;
+        }
+        let len = self.reinterpret::<T>().len();
+        Some(unsafe { core::slice::from_raw_parts(self.as_ptr::<T>().as_ptr(), len) })
+    }
+}
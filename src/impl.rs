@@ -1,6 +1,21 @@
-use core::{alloc::Layout, any::type_name, mem::MaybeUninit, ptr::NonNull};
+#[cfg(not(no_global_oom_handling))]
+use core::alloc::LayoutError;
+use core::{
+    alloc::Layout,
+    any::type_name,
+    borrow::Borrow,
+    ffi::c_void,
+    mem::MaybeUninit,
+    num::NonZero,
+    ops::{Index, IndexMut, Range},
+    ptr::NonNull,
+    slice::SliceIndex,
+};
 
-use crate::alloc_shim::{AllocError, Allocator, Global};
+use crate::{
+    alloc_shim::{AllocError, Allocator, Global},
+    BatchResize,
+};
 
 /// An allocation is management representation of some allocated memory.
 ///
@@ -8,32 +23,68 @@ use crate::alloc_shim::{AllocError, Allocator, Global};
 /// The memory backing this allocation is deallocated when the allocation is dropped.
 /// In contrast, no validity or initialization state of the memory is implied by
 /// existance of an [Allocation].
+///
+/// The allocator is stored by value and has to be sized. To allocate from a type-erased allocator, use a
+/// reference such as `&dyn Allocator`, which implements [`Allocator`] itself:
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly-std-conversions", feature(allocator_api))]
+/// # use core::alloc::Layout;
+/// # use allocator_api2::alloc::{Allocator, Global};
+/// # use untyped_box::Allocation;
+/// let allocator: &dyn Allocator = &Global;
+/// let alloc = Allocation::new_in(Layout::new::<u32>(), allocator);
+/// ```
 pub struct Allocation<A: Allocator = Global> {
-    // TODO: should be a Unique pointer!
-    ptr: NonNull<u8>,
+    ptr: Unique,
     layout: Layout,
+    // The layout last requested by the user. Always fits into `layout`, which describes the memory block.
+    requested: Layout,
     alloc: A,
+    #[cfg(feature = "generation")]
+    generation: u64,
+}
+
+/// How a reallocation was performed, as reported by [`Allocation::try_realloc_in_place_or_copy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reallocation {
+    /// The memory block stayed at the same address. Pointers into the allocation remain valid.
+    InPlace,
+    /// The contents were copied to a new memory block and the old block was deallocated.
+    Moved,
+}
+
+// A pointer owning the memory it points to, similar to the unstable `core::ptr::Unique`.
+// Sending or sharing the pointer is equivalent to sending or sharing the owned bytes, which is always fine.
+// Hence, an `Allocation` is `Send` and `Sync` exactly when its allocator is.
+#[derive(Clone, Copy)]
+struct Unique(NonNull<u8>);
+
+impl Unique {
+    fn get(self) -> NonNull<u8> {
+        self.0
+    }
 }
 
+unsafe impl Send for Unique {}
+unsafe impl Sync for Unique {}
+
 // TODO: There is a bit of a mismatch here. In essence, we are losing information.
 // For example, requesting an allocation for some `Layout::new::<T>()` that results in the allocator
 // giving us more memory than we asked for might make later checks when trying to convert to a `Box`
 // fail on size mismatch.
 // We might have to blow up the allocation struct to reconstruct [Memory fitting] information.
 // [Memory fitting]: https://doc.rust-lang.org/nightly/alloc/alloc/trait.Allocator.html#memory-fitting
-fn match_allocated_size(ptr: NonNull<[u8]>, layout: Layout) -> (NonNull<u8>, Layout) {
+fn match_allocated_size(ptr: NonNull<[u8]>, layout: Layout) -> (Unique, Layout) {
     let actual_layout = unsafe { Layout::from_size_align_unchecked(ptr.len(), layout.align()) };
     debug_assert!(actual_layout.size() >= layout.size());
-    (ptr.cast(), actual_layout)
+    (Unique(ptr.cast()), actual_layout)
 }
-fn allocate(alloc: &impl Allocator, layout: Layout) -> Result<(NonNull<u8>, Layout), AllocError> {
+fn allocate(alloc: &impl Allocator, layout: Layout) -> Result<(Unique, Layout), AllocError> {
     let ptr = alloc.allocate(layout)?;
     Ok(match_allocated_size(ptr, layout))
 }
-fn allocate_zeroed(
-    alloc: &impl Allocator,
-    layout: Layout,
-) -> Result<(NonNull<u8>, Layout), AllocError> {
+fn allocate_zeroed(alloc: &impl Allocator, layout: Layout) -> Result<(Unique, Layout), AllocError> {
     let ptr = alloc.allocate_zeroed(layout)?;
     Ok(match_allocated_size(ptr, layout))
 }
@@ -42,7 +93,7 @@ unsafe fn grow(
     ptr: NonNull<u8>,
     old_layout: Layout,
     new_layout: Layout,
-) -> Result<(NonNull<u8>, Layout), AllocError> {
+) -> Result<(Unique, Layout), AllocError> {
     let ptr = alloc.grow(ptr, old_layout, new_layout)?;
     Ok(match_allocated_size(ptr, new_layout))
 }
@@ -51,7 +102,7 @@ unsafe fn grow_zeroed(
     ptr: NonNull<u8>,
     old_layout: Layout,
     new_layout: Layout,
-) -> Result<(NonNull<u8>, Layout), AllocError> {
+) -> Result<(Unique, Layout), AllocError> {
     let ptr = alloc.grow_zeroed(ptr, old_layout, new_layout)?;
     Ok(match_allocated_size(ptr, new_layout))
 }
@@ -60,7 +111,7 @@ unsafe fn shrink(
     ptr: NonNull<u8>,
     old_layout: Layout,
     new_layout: Layout,
-) -> Result<(NonNull<u8>, Layout), AllocError> {
+) -> Result<(Unique, Layout), AllocError> {
     let ptr = alloc.shrink(ptr, old_layout, new_layout)?;
     Ok(match_allocated_size(ptr, new_layout))
 }
@@ -82,6 +133,7 @@ impl Allocation {
     /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
     /// See [`Self::try_new_in`] for a version that returns an error instead.
     // Forwards to alloc, handles layout.size() == 0 with a dangling ptr
+    #[cfg(not(no_global_oom_handling))]
     pub fn new(layout: Layout) -> Self {
         Self::new_in(layout, Global)
     }
@@ -97,6 +149,7 @@ impl Allocation {
     ///
     /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
     /// See [`Self::try_zeroed_in`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
     pub fn zeroed(layout: Layout) -> Self {
         Self::zeroed_in(layout, Global)
     }
@@ -106,17 +159,164 @@ impl Allocation {
     pub fn try_zeroed(layout: Layout) -> Result<Self, AllocError> {
         Self::try_zeroed_in(layout, Global)
     }
+    /// Allocate new memory for a `T` and move `value` into it.
+    ///
+    /// This is the untyped analogue of [`Box::new`](alloc::boxed::Box::new). The memory is initialized,
+    /// so converting back with [`try_into_box`](Self::try_into_box) can be followed by
+    /// [`Box::assume_init`](alloc::boxed::Box::assume_init). The value is not dropped together with the allocation.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_value<T>(value: T) -> Self {
+        Self::from_value_in(value, Global)
+    }
+    /// Create a zero-sized allocation aligned for `T`.
+    ///
+    /// The allocation is backed by [`NonNull::dangling`] and does not allocate. This is useful as a placeholder that
+    /// can later be grown with [`realloc`](Self::realloc).
+    pub fn dangling<T>() -> Self {
+        let layout = Layout::new::<[T; 0]>();
+        // SAFETY: The global allocator does not need to deallocate zero-sized allocations
+        unsafe { Self::from_parts(NonNull::<T>::dangling().cast(), layout) }
+    }
+    /// Allocate new memory for a `T`.
+    ///
+    /// This is a shortcut for `Allocation::new(Layout::new::<T>())`.
+    ///
+    /// ```
+    /// # use untyped_box::Allocation;
+    /// let mut alloc = Allocation::new_for::<u64>();
+    /// alloc.write_value(42u64);
+    /// let boxed = alloc.try_into_box::<u64>().unwrap();
+    /// assert_eq!(*unsafe { boxed.assume_init() }, 42);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_for<T>() -> Self {
+        Self::new_for_in::<T>(Global)
+    }
+    /// Allocate new zeroed-out memory for a `T`.
+    ///
+    /// This is a shortcut for `Allocation::zeroed(Layout::new::<T>())`.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn zeroed_for<T>() -> Self {
+        Self::zeroed_for_in::<T>(Global)
+    }
+    /// Allocate new memory for `cap` bytes with an alignment of `1`.
+    ///
+    /// This is the untyped analogue of [`Vec::with_capacity`](alloc::vec::Vec::with_capacity) for bytes. Use
+    /// [`reserve`](Self::reserve) to grow the allocation as it fills up.
+    ///
+    /// # Panics
+    ///
+    /// If `cap` exceeds `isize::MAX`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn with_capacity_bytes(cap: usize) -> Self {
+        Self::with_capacity_bytes_in(cap, Global)
+    }
+    /// Allocate new memory for an array of `n` elements of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the size of the array overflows, see [`Self::new_array_checked`].
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_array<T>(n: usize) -> Self {
+        Self::new_array_in::<T>(n, Global)
+    }
+    /// Allocate new memory for an array of `n` elements of type `T`.
+    ///
+    /// Same as [`Self::new_array`] but returns an error if the size of the array overflows.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_array_checked<T>(n: usize) -> Result<Self, LayoutError> {
+        Self::new_array_checked_in::<T>(n, Global)
+    }
+    /// Allocate new zeroed-out memory for an array of `n` elements of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the size of the array overflows, see [`Self::try_zeroed_array`].
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn zeroed_array<T>(n: usize) -> Self {
+        Self::zeroed_array_in::<T>(n, Global)
+    }
+    /// Allocate new zeroed-out memory for an array of `n` elements of type `T`.
+    ///
+    /// Same as [`Self::zeroed_array`] but returns an error if the size of the array overflows.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn try_zeroed_array<T>(n: usize) -> Result<Self, LayoutError> {
+        Self::try_zeroed_array_in::<T>(n, Global)
+    }
+    /// Allocate new memory and copy the bytes of `src` into it.
+    ///
+    /// The layout of the allocation has the size `src.len()` and an alignment of `1`.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    /// See [`Self::try_from_slice`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_slice(src: &[u8]) -> Self {
+        Self::from_slice_in(src, Global)
+    }
+    /// Allocate new memory and copy the bytes of `src` into it.
+    ///
+    /// Same as [`Self::from_slice`] but returns an error when memory could not be allocated.
+    pub fn try_from_slice(src: &[u8]) -> Result<Self, AllocError> {
+        Self::try_from_slice_in(src, Global)
+    }
+    /// Allocate new memory fitting the layout of `value`.
+    ///
+    /// The allocated layout is [`Layout::for_value`], which also supports unsized values such as slices.
+    /// The contents of `value` are not copied.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn for_value<T: ?Sized>(value: &T) -> Self {
+        Self::for_value_in(value, Global)
+    }
     /// Split the allocation into its raw parts.
     ///
     /// Deallocating the allocation is the responsibility of the caller. The returned
     /// pointer can be passed to [`alloc::alloc::dealloc`] if the returned layout indicates `size() > 0`.
     /// If the allocated memory is 0 sized, the pointer does not need to be deallocated.
     ///
-    /// See also [`Self::into_parts_with_alloc`] for an allocator-aware version.
+    /// See also [`Self::into_parts_with_alloc`] for an allocator-aware version, and which layouts the memory fits.
     pub fn into_parts(self) -> (NonNull<u8>, Layout) {
         let (ptr, layout, _) = Self::into_parts_with_alloc(self);
         (ptr, layout)
     }
+    /// Consumes and leaks the allocation, returning a mutable reference to its bytes.
+    ///
+    /// The memory is never deallocated. This is symmetric to [`Box::leak`](alloc::boxed::Box::leak).
+    /// The returned slice is `layout().size()` bytes long.
+    ///
+    /// See also [`Self::leak_in`] for an allocator-aware version.
+    pub fn leak(self) -> &'static mut [MaybeUninit<u8>] {
+        Self::leak_in(self)
+    }
     /// Constructs an [`Allocation`] from a pointer and layout information.
     ///
     /// # Safety
@@ -141,7 +341,22 @@ impl<A: Allocator> Allocation<A> {
     ///
     /// In particular, the pointer does not in itself materialize a reference to the underlying storage for the purpose of the aliasing model.
     pub fn as_ptr<T>(&self) -> NonNull<T> {
-        self.ptr.cast()
+        self.ptr.get().cast()
+    }
+    /// Gets a pointer to the allocation, cast to a pointer to `T`.
+    ///
+    /// This is an alias of [`as_ptr`](Self::as_ptr), named after [`NonNull::cast`].
+    ///
+    /// ```
+    /// # use core::alloc::Layout;
+    /// # use untyped_box::Allocation;
+    /// let alloc = Allocation::new(Layout::new::<u32>());
+    /// let ptr = alloc.cast::<u32>();
+    /// unsafe { ptr.write(42) };
+    /// assert_eq!(unsafe { ptr.read() }, 42);
+    /// ```
+    pub fn cast<T>(&self) -> NonNull<T> {
+        self.ptr.get().cast()
     }
     /// View the underlying storage as a possibly uninitialized `T`.
     ///
@@ -159,7 +374,7 @@ impl<A: Allocator> Allocation<A> {
             "allocation not aligned for a {}",
             type_name::<T>()
         );
-        unsafe { &*self.ptr.as_ptr().cast() }
+        unsafe { &*self.ptr.get().as_ptr().cast() }
     }
     /// View the underlying storage as a possibly uninitialized `T`.
     ///
@@ -177,7 +392,109 @@ impl<A: Allocator> Allocation<A> {
             "allocation not aligned for a {}",
             type_name::<T>()
         );
-        unsafe { &mut *self.ptr.as_ptr().cast() }
+        unsafe { &mut *self.ptr.get().as_ptr().cast() }
+    }
+    /// Returns `true` if the allocation is aligned enough to contain a `T`.
+    ///
+    /// See also [`Self::fits`] to additionally check the size.
+    pub fn is_aligned_for<T>(&self) -> bool {
+        self.layout.align() >= align_of::<T>()
+    }
+    /// Returns `true` if the allocation is large enough and aligned enough to contain a `T`.
+    ///
+    /// If this returns `true`, [`as_uninit_ref`](Self::as_uninit_ref) and [`as_uninit_mut`](Self::as_uninit_mut) do not panic.
+    pub fn fits<T>(&self) -> bool {
+        self.fits_layout(Layout::new::<T>())
+    }
+    /// Returns `true` if the allocation is large enough and aligned enough for the given layout.
+    pub fn fits_layout(&self, layout: Layout) -> bool {
+        self.layout.size() >= layout.size() && self.layout.align() >= layout.align()
+    }
+    /// Move a value into the start of the underlying storage.
+    ///
+    /// Any previous contents are overwritten without being dropped. The returned reference
+    /// points to the now initialized value.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is too small, or not aligned enough to contain a `T`.
+    pub fn write_value<T>(&mut self, value: T) -> &mut T {
+        self.as_uninit_mut::<T>().write(value)
+    }
+    /// Copy a slice into the start of the underlying storage.
+    ///
+    /// Any previous contents are overwritten. The returned slice points to the now initialized elements.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is too small, or not aligned enough to contain `src.len()` elements of type `T`.
+    pub fn write_slice<T: Copy>(&mut self, src: &[T]) -> &mut [T] {
+        assert!(
+            self.fits_layout(Layout::for_value(src)),
+            "allocation does not fit {} elements of {}",
+            src.len(),
+            type_name::<T>()
+        );
+        let dst = self.ptr.get().as_ptr().cast::<T>();
+        unsafe {
+            core::ptr::copy_nonoverlapping(src.as_ptr(), dst, src.len());
+            core::slice::from_raw_parts_mut(dst, src.len())
+        }
+    }
+    /// Copy bytes into the start of the underlying storage using volatile writes.
+    ///
+    /// Each byte is written with [`write_volatile`](core::ptr::write_volatile), so the compiler does not elide or
+    /// merge the writes even if the memory is never read again. This is useful when the allocation is backed by
+    /// memory-mapped device registers.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is smaller than `src`.
+    pub fn volatile_copy_from_slice(&mut self, src: &[u8]) {
+        assert!(
+            src.len() <= self.layout.size(),
+            "allocation does not fit {} bytes",
+            src.len()
+        );
+        let dst = self.ptr.get().as_ptr();
+        for (i, &byte) in src.iter().enumerate() {
+            unsafe { dst.add(i).write_volatile(byte) };
+        }
+    }
+    /// Initialize a value in place at the start of the underlying storage.
+    ///
+    /// `f` is called with a view of the storage as a possibly uninitialized `T`, see
+    /// [`as_uninit_mut`](Self::as_uninit_mut). In contrast to [`write_value`](Self::write_value), the value does
+    /// not have to be constructed on the stack first.
+    ///
+    /// # Safety
+    ///
+    /// `f` must fully initialize the value.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is too small, or not aligned enough to contain a `T`.
+    pub unsafe fn init_with<T>(&mut self, f: impl FnOnce(&mut MaybeUninit<T>)) -> &mut T {
+        let uninit = self.as_uninit_mut::<T>();
+        f(uninit);
+        unsafe { uninit.assume_init_mut() }
+    }
+    /// Read a value from the start of the underlying storage.
+    ///
+    /// This performs a bitwise copy, as if by [`core::ptr::read`], and leaves the storage unchanged.
+    /// Hence, the value is logically duplicated: care must be taken to not use or drop both the returned
+    /// value and the copy still in the allocation if `T` is not [`Copy`].
+    ///
+    /// # Safety
+    ///
+    /// A valid `T` must have previously been initialized at the start of the allocation, for example
+    /// with [`write_value`](Self::write_value).
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is too small, or not aligned enough to contain a `T`.
+    pub unsafe fn read_value<T>(&self) -> T {
+        unsafe { self.as_uninit_ref::<T>().assume_init_read() }
     }
     /// View the allocation as a pointer to a slice of possibly uninitialized bytes.
     ///
@@ -190,16 +507,151 @@ impl<A: Allocator> Allocation<A> {
     /// Hence, these two methods can be intermixed.
     pub fn as_slice(&self) -> NonNull<[MaybeUninit<u8>]> {
         let ptr = core::ptr::slice_from_raw_parts_mut(
-            self.ptr.as_ptr().cast::<MaybeUninit<u8>>(),
+            self.ptr.get().as_ptr().cast::<MaybeUninit<u8>>(),
             self.layout.size(),
         );
         unsafe { NonNull::new_unchecked(ptr) }
     }
+    /// Get the pointer to the allocation for passing it to C code.
+    ///
+    /// ```
+    /// # use core::{alloc::Layout, ffi::c_void};
+    /// # use untyped_box::Allocation;
+    /// // Stands in for a function implemented in C
+    /// extern "C" fn fill(buf: *mut c_void, len: usize) {
+    ///     unsafe { core::ptr::write_bytes(buf.cast::<u8>(), 0xff, len) };
+    /// }
+    /// let alloc = Allocation::new(Layout::new::<[u8; 4]>());
+    /// fill(alloc.as_c_void(), alloc.len());
+    /// assert_eq!(unsafe { alloc.read_value::<[u8; 4]>() }, [0xff; 4]);
+    /// ```
+    pub fn as_c_void(&self) -> *mut c_void {
+        self.ptr.get().as_ptr().cast()
+    }
+    /// View the allocation as a slice of possibly uninitialized bytes.
+    ///
+    /// In contrast to [`as_slice`](Self::as_slice), this materializes a reference to the whole allocation.
+    pub fn as_bytes(&self) -> &[MaybeUninit<u8>] {
+        unsafe { self.as_slice().as_ref() }
+    }
+    /// View the allocation as a mutable slice of possibly uninitialized bytes.
+    ///
+    /// In contrast to [`as_slice`](Self::as_slice), this materializes a reference to the whole allocation.
+    pub fn as_bytes_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        unsafe { self.as_slice().as_mut() }
+    }
+    /// View the allocation as a slice of initialized bytes, for example to hash or compare its contents.
+    /// To use the allocation as the key of a map, wrap it in a [`ByteView`](crate::ByteView) instead.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the allocation must be initialized.
+    pub unsafe fn as_init_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.ptr.get().as_ptr(), self.layout.size()) }
+    }
+    /// View the allocation as a slice of as many possibly uninitialized `T` as fit into it.
+    ///
+    /// The length of the slice is `self.layout().size() / size_of::<T>()`, or `0` if `T` is zero-sized.
+    /// Trailing bytes that do not fit a whole `T` are not part of the slice.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is not aligned enough to contain a `T`.
+    pub fn reinterpret<T>(&self) -> &[MaybeUninit<T>] {
+        assert!(
+            self.is_aligned_for::<T>(),
+            "allocation not aligned for a {}",
+            type_name::<T>()
+        );
+        let len = self.layout.size().checked_div(size_of::<T>()).unwrap_or(0);
+        unsafe { core::slice::from_raw_parts(self.ptr.get().as_ptr().cast(), len) }
+    }
+    /// View the allocation as chunks of `N` bytes, starting at the beginning, and a remainder.
+    ///
+    /// The remainder is shorter than `N` bytes. This mirrors `<[T]>::as_chunks`.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is `0`.
+    pub fn as_chunks<const N: usize>(&self) -> (&[[MaybeUninit<u8>; N]], &[MaybeUninit<u8>]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let bytes = self.as_bytes();
+        let len = bytes.len() / N;
+        let (chunks, remainder) = bytes.split_at(len * N);
+        // SAFETY: `chunks` consists of exactly `len` arrays of `N` bytes, which have an alignment of `1`
+        let chunks = unsafe { core::slice::from_raw_parts(chunks.as_ptr().cast(), len) };
+        (chunks, remainder)
+    }
+    /// Gets the start and one-past-the-end pointers of the allocation.
+    ///
+    /// For zero-sized allocations, both pointers are equal. This is useful for passing a `[begin, end)` pair to C APIs.
+    ///
+    /// The same validity and aliasing considerations as for [`as_ptr`](Self::as_ptr) apply.
+    pub fn as_ptr_range(&self) -> Range<NonNull<u8>> {
+        // SAFETY: the allocation is valid for `size` bytes, so the end pointer is in bounds or one past the end
+        let end = unsafe { self.ptr.get().add(self.layout.size()) };
+        self.ptr.get()..end
+    }
+    /// Gets the address of the allocation, without provenance.
+    ///
+    /// This is useful for logging, where an integer is preferable to a pointer that could be dereferenced.
+    pub fn addr(&self) -> NonZero<usize> {
+        self.ptr.get().addr()
+    }
+    /// Returns `true` if the address of `ptr` lies within the allocation.
+    ///
+    /// Only addresses are compared, the provenance of `ptr` is ignored. The one-past-the-end address is not
+    /// contained in the allocation, in particular no pointer is contained in a zero-sized allocation.
+    pub fn contains_ptr<T>(&self, ptr: *const T) -> bool {
+        let start = self.ptr.get().as_ptr().addr();
+        let addr = ptr.addr();
+        addr >= start && addr - start < self.layout.size()
+    }
+    /// Transforms every byte of the allocation in place, e.g. to apply an XOR mask.
+    ///
+    /// `f` is called once per byte, in order, and its result is written back.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the allocation must be initialized.
+    pub unsafe fn map_in_place(&mut self, mut f: impl FnMut(u8) -> u8) {
+        for byte in self.as_bytes_mut() {
+            let value = unsafe { byte.assume_init() };
+            byte.write(f(value));
+        }
+    }
+    /// Copies the bytes in `src` to the offset `dest` within the allocation.
+    ///
+    /// The ranges may overlap, see [`slice::copy_within`](<[u8]>::copy_within).
+    ///
+    /// # Panics
+    ///
+    /// If either the source or the destination range exceeds the size of the allocation.
+    pub fn copy_within(&mut self, src: Range<usize>, dest: usize) {
+        self.as_bytes_mut().copy_within(src, dest);
+    }
+    /// Returns `true` if both allocations have the same size and contain identical bytes.
+    ///
+    /// The alignment of the allocations is not compared.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of both allocations must be initialized.
+    pub unsafe fn content_eq<B: Allocator>(&self, other: &Allocation<B>) -> bool {
+        unsafe { self.as_init_bytes() == other.as_init_bytes() }
+    }
     /// Reallocates memory to a new layout.
     ///
     /// If the newly requested layout is larger than the currently allocated layout, existing (possibly uninitialized) bytes are preserved.
     /// Newly allocated bytes are uninitialized.
     ///
+    /// If the current memory block already [fits](Self::fits_layout) the new layout, for example because the allocator handed out
+    /// more memory than requested, the memory is not reallocated and [`Self::layout`] is unchanged. In this case, bytes
+    /// past the previously requested size keep their old contents: shrinking and then growing back exposes the bytes
+    /// written before shrinking. Use [`realloc_zeroed`](Self::realloc_zeroed) to clear them.
+    ///
+    /// Reallocating to a zero-sized layout always deallocates the memory block, leaving a dangling pointer.
+    ///
     /// Any pointers to the managed memory are invalidated on return.
     ///
     /// # Panics
@@ -207,122 +659,1015 @@ impl<A: Allocator> Allocation<A> {
     /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
     /// See [`Self::try_realloc`] for a version that returns an error instead.
     // Calls either grow or shrink, compares against stored layout
+    #[cfg(not(no_global_oom_handling))]
     pub fn realloc(&mut self, new_layout: Layout) {
         let () = self
             .try_realloc(new_layout)
-            .unwrap_or_else(|AllocError| alloc::alloc::handle_alloc_error(new_layout));
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
     }
-    /// Reallocates memory to a new layout.
+    /// Reallocates memory to exactly the new layout.
     ///
-    /// If the newly requested layout is larger than the currently allocated layout, existing (possibly uninitialized) bytes are preserved.
-    /// Newly allocated bytes are zeroed.
+    /// In contrast to [`realloc`](Self::realloc), the allocator is called even if the current memory block already fits
+    /// the new layout, so shrinking returns the slack to the allocator. In contrast to [`reserve`](Self::reserve), no
+    /// additional capacity is requested. This gives predictable memory usage at the cost of more reallocations.
     ///
     /// Any pointers to the managed memory are invalidated on return.
     ///
     /// # Panics
     ///
     /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
-    /// See [`Self::try_realloc_zeroed`] for a version that returns an error instead.
-    pub fn realloc_zeroed(&mut self, new_layout: Layout) {
+    /// See [`Self::try_realloc_exact`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn realloc_exact(&mut self, new_layout: Layout) {
         let () = self
-            .try_realloc_zeroed(new_layout)
-            .unwrap_or_else(|AllocError| alloc::alloc::handle_alloc_error(new_layout));
+            .try_realloc_exact(new_layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
     }
-    /// Get the layout of the underlying allocation.
+    /// Reallocates memory to fit exactly one `T`.
     ///
-    /// This layout is guaranteed to be at least as large as previously requested from [`new`](Self::new) or [`realloc`](Self::realloc) and
-    /// at least as strictly aligned, but might indicate more available memory.
-    pub fn layout(&self) -> Layout {
-        self.layout
-    }
-}
-/// Methods using the allocator-api or shim
-impl<A: Allocator> Allocation<A> {
-    /// Allocate new memory for the given layout in a given allocator.
+    /// This is the same as [`realloc`](Self::realloc) with `Layout::new::<T>()`.
     ///
-    /// The pointer backing the allocation is valid for reads and writes of `layout.size()` bytes and this
-    /// memory region does not alias any other existing allocation.
+    /// # Panics
     ///
-    /// The pointer is guaranteed to be aligned to `layout.align()` but several systems align memory more
-    /// lax when a small alignment is requested.
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn realloc_for<T>(&mut self) {
+        self.realloc(Layout::new::<T>());
+    }
+    /// Reallocates memory to fit an array of `n` elements of type `T`.
     ///
-    /// Memory is not initialized or zeroed, try [`Self::zeroed_in`] instead.
+    /// This is the same as [`realloc`](Self::realloc) with `Layout::array::<T>(n)`. Returns an error if the size
+    /// of the array overflows, in which case the allocation is unchanged.
     ///
     /// # Panics
     ///
-    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
-    /// See [`Self::try_new_in`] for a version that returns an error instead.
-    pub fn new_in(layout: Layout, alloc: A) -> Self {
-        Self::try_new_in(layout, alloc)
-            .unwrap_or_else(|AllocError| alloc::alloc::handle_alloc_error(layout))
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn realloc_array_for<T>(&mut self, n: usize) -> Result<(), LayoutError> {
+        self.realloc(Layout::array::<T>(n)?);
+        Ok(())
     }
-    /// Allocate new memory for the given layout in a given allocator.
+    /// Reallocates memory to an alignment of at least `new_align`, keeping the current size.
     ///
-    /// Returns an error when no memory could be allocated.
-    pub fn try_new_in(layout: Layout, alloc: A) -> Result<Self, AllocError> {
-        let (ptr, layout) = allocate(&alloc, layout)?;
-        Ok(Self { ptr, layout, alloc })
-    }
-    /// Allocate new zeroed-out memory for the given layout in a given allocator.
+    /// Existing bytes are preserved. If the allocation is already aligned enough, this does nothing, and in particular
+    /// leaves the [requested layout](Self::requested_layout) unchanged.
     ///
     /// # Panics
     ///
-    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
-    /// See [`Self::try_zeroed_in`] for a version that returns an error instead.
-    pub fn zeroed_in(layout: Layout, alloc: A) -> Self {
-        Self::try_zeroed_in(layout, alloc)
-            .unwrap_or_else(|AllocError| alloc::alloc::handle_alloc_error(layout))
+    /// If `new_align` is not a power of two, or the size rounded up to `new_align` overflows `isize`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn align_to(&mut self, new_align: usize) {
+        assert!(
+            new_align.is_power_of_two(),
+            "alignment must be a power of two"
+        );
+        if new_align <= self.layout.align() {
+            return;
+        }
+        let new_layout =
+            Layout::from_size_align(self.layout.size(), new_align).expect("invalid alignment");
+        self.realloc(new_layout);
     }
-    /// Allocate new zeroed-out memory for the given layout in a given allocator.
+    /// Reallocates memory to a new size in bytes, keeping the current alignment.
     ///
-    /// Returns an error when no memory could be allocated.
-    pub fn try_zeroed_in(layout: Layout, alloc: A) -> Result<Self, AllocError> {
-        let (ptr, layout) = allocate_zeroed(&alloc, layout)?;
-        Ok(Self { ptr, layout, alloc })
+    /// This is the same as [`realloc`](Self::realloc) with a layout of `new_size` bytes and the alignment of [`Self::layout`].
+    /// Resizing to `0` bytes deallocates the memory block, leaving a dangling pointer.
+    ///
+    /// # Panics
+    ///
+    /// If `new_size`, rounded up to the alignment, overflows `isize`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_bytes(&mut self, new_size: usize) {
+        self.realloc(self.resized_layout(new_size));
     }
-    /// Split the allocation into its raw parts including the allocator.
+    /// Reallocates memory to a new size in bytes, keeping the current alignment.
     ///
-    /// Deallocating the allocation is the responsibility of the caller. The returned
-    /// pointer can be passed to `alloc.deallocate()`.
-    pub fn into_parts_with_alloc(self) -> (NonNull<u8>, Layout, A) {
-        let me = core::mem::ManuallyDrop::new(self);
-        let alloc = unsafe { core::ptr::read(&me.alloc) };
-        (me.ptr, me.layout, alloc)
+    /// Same as [`Self::resize_bytes`], but newly allocated bytes are zeroed, see [`Self::realloc_zeroed`].
+    ///
+    /// # Panics
+    ///
+    /// If `new_size`, rounded up to the alignment, overflows `isize`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_bytes_zeroed(&mut self, new_size: usize) {
+        self.realloc_zeroed(self.resized_layout(new_size));
     }
-    /// Constructs an [`Allocation`] from a pointer and layout information in the given allocator.
+    /// Reallocates memory to fit `new_len` elements of type `T`, keeping at least the current alignment.
     ///
-    /// # Safety
+    /// This is the typed analogue of [`resize_bytes`](Self::resize_bytes). Existing bytes up to the smaller of the
+    /// old and new size are preserved, newly allocated bytes are uninitialized. In contrast to
+    /// [`realloc_array_for`](Self::realloc_array_for), the alignment is never lowered below [`Self::layout`].
     ///
-    /// The pointer must point to [*currently-allocated*] memory from the given allocator, and `layout`
-    /// [*fits*] that memory.
+    /// # Panics
     ///
-    /// [*currently-allocated*]: Allocator#currently-allocated-memory
-    /// [*fits*]: Allocator#memory-fitting
-    pub unsafe fn from_parts_in(ptr: NonNull<u8>, layout: Layout, alloc: A) -> Self {
-        Self { ptr, layout, alloc }
+    /// If the size of `new_len` elements overflows `isize`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_elements<T>(&mut self, new_len: usize) {
+        self.realloc(self.resized_array_layout::<T>(new_len));
     }
-    /// Reallocates memory to a new layout.
+    /// Reallocates memory to fit `new_len` elements of type `T`, keeping at least the current alignment.
     ///
-    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
-    /// pointers remain valid and no memory is deallocated.
+    /// Same as [`Self::resize_elements`], but newly allocated bytes are zeroed, see [`Self::realloc_zeroed`].
     ///
-    /// # See also
+    /// # Panics
+    ///
+    /// If the size of `new_len` elements overflows `isize`.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_elements_zeroed<T>(&mut self, new_len: usize) {
+        self.realloc_zeroed(self.resized_array_layout::<T>(new_len));
+    }
+    #[cfg(not(no_global_oom_handling))]
+    fn resized_array_layout<T>(&self, new_len: usize) -> Layout {
+        Layout::array::<T>(new_len)
+            .and_then(|layout| layout.align_to(self.layout.align()))
+            .expect("new size overflows isize")
+    }
+    #[cfg(not(no_global_oom_handling))]
+    fn resized_layout(&self, new_size: usize) -> Layout {
+        Layout::from_size_align(new_size, self.layout.align()).expect("new size overflows isize")
+    }
+    /// Reserves room for at least `additional` more bytes after the [requested size](Self::requested_layout).
+    ///
+    /// The requested size is increased by `additional`, while the alignment is kept. Like a [`Vec`](alloc::vec::Vec),
+    /// the allocation grows to at least double its current size when it has to be reallocated, so that repeatedly
+    /// reserving a few bytes does not reallocate every time.
+    ///
+    /// Existing (possibly uninitialized) bytes are preserved, newly allocated bytes are uninitialized.
+    ///
+    /// # Panics
+    ///
+    /// If the new size overflows.
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_reserve`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve(&mut self, additional: usize) {
+        let Some((_, amortized)) = self.reserve_layouts(additional) else {
+            panic!("capacity overflow");
+        };
+        let () = self
+            .try_reserve(additional)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(amortized));
+    }
+    /// Shrinks the memory block to the [requested layout](Self::requested_layout).
+    ///
+    /// This reclaims memory that the allocator handed out in excess of the requested size, or that was kept
+    /// when [reallocating](Self::realloc) to a smaller layout. The allocator might still hand out more memory
+    /// than requested, see [`Self::layout`].
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_shrink_to_fit`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn shrink_to_fit(&mut self) {
+        let requested = self.requested;
+        let () = self
+            .try_shrink_to_fit()
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(requested));
+    }
+    /// Shrinks the memory block to at least `min_capacity_bytes`, keeping the alignment.
+    ///
+    /// Like [`Vec::shrink_to`](alloc::vec::Vec::shrink_to), the memory block never shrinks below the
+    /// [requested size](Self::requested_layout), which is left unchanged. If the block is already no larger than
+    /// `min_capacity_bytes`, this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_shrink_to`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn shrink_to(&mut self, min_capacity_bytes: usize) {
+        let target = min_capacity_bytes.max(self.requested.size());
+        let () = self
+            .try_shrink_to(min_capacity_bytes)
+            .unwrap_or_else(|AllocError| {
+                let layout =
+                    unsafe { Layout::from_size_align_unchecked(target, self.layout.align()) };
+                crate::alloc_shim::handle_alloc_error(layout)
+            });
+    }
+    /// Grows the allocation to a new layout.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is smaller than [`Self::layout`].
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_grow`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn grow(&mut self, new_layout: Layout) {
+        let () = self
+            .try_grow(new_layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
+    }
+    /// Grows the allocation to a new layout, zeroing the newly allocated bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is smaller than [`Self::layout`].
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_grow_zeroed`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn grow_zeroed(&mut self, new_layout: Layout) {
+        let () = self
+            .try_grow_zeroed(new_layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
+    }
+    /// Shrinks the allocation to a new layout.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is larger than [`Self::layout`].
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_shrink`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn shrink(&mut self, new_layout: Layout) {
+        let () = self
+            .try_shrink(new_layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
+    }
+    /// Reallocates memory to a new layout.
+    ///
+    /// If the newly requested layout is larger than the currently allocated layout, existing (possibly uninitialized) bytes are preserved.
+    /// All bytes past the previously [requested size](Self::requested_size) are zeroed, including those already part of the memory block.
+    ///
+    /// As with [`realloc`](Self::realloc), the memory is not reallocated if the current memory block already
+    /// [fits](Self::fits_layout) the new layout.
+    ///
+    /// Any pointers to the managed memory are invalidated on return.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_realloc_zeroed`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn realloc_zeroed(&mut self, new_layout: Layout) {
+        let () = self
+            .try_realloc_zeroed(new_layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(new_layout));
+    }
+    /// Start batching several resize requests into a single reallocation.
+    ///
+    /// This is useful when the final size is only known after several incremental steps, e.g. in a loop.
+    /// See [`BatchResize`] for details.
+    pub fn begin_batch(&mut self) -> BatchResize<'_, A> {
+        BatchResize::new(self)
+    }
+    /// Get the layout of the underlying allocation.
+    ///
+    /// This layout is guaranteed to be at least as large as previously requested from [`new`](Self::new) or [`realloc`](Self::realloc) and
+    /// at least as strictly aligned, but might indicate more available memory.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+    /// A counter that changes every time the allocation is reallocated.
+    ///
+    /// Reallocating invalidates all pointers into the allocation, even if the address happens to stay the same.
+    /// Snapshot the generation when deriving a pointer and compare it before use to detect use of stale pointers.
+    /// Reallocating to the layout the allocation already has is a no-op and does not change the generation.
+    ///
+    /// Only available with the `generation` feature, which adds the counter to every allocation.
+    #[cfg(feature = "generation")]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    // Called whenever the memory block is replaced. Compiles to nothing without the `generation` feature.
+    fn bump_generation(&mut self) {
+        #[cfg(feature = "generation")]
+        {
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+    /// Get the layout last requested from [`new`](Self::new) or [`realloc`](Self::realloc).
+    ///
+    /// The allocation always [fits](Self::fits_layout) this layout, but [`Self::layout`] might indicate more available memory
+    /// or a stricter alignment.
+    pub fn requested_layout(&self) -> Layout {
+        self.requested
+    }
+    /// The size of the memory block reported by the allocator, i.e. `self.layout().size()`.
+    ///
+    /// This might be larger than [`Self::requested_size`]. The slack can be used without reallocating.
+    pub fn usable_size(&self) -> usize {
+        self.layout.size()
+    }
+    /// The size last requested, i.e. `self.requested_layout().size()`.
+    pub fn requested_size(&self) -> usize {
+        self.requested.size()
+    }
+    /// The size of the allocation in bytes.
+    ///
+    /// Same as [`Self::size`]. See [`Self::layout`] for guarantees about the reported size.
+    pub fn len(&self) -> usize {
+        self.layout.size()
+    }
+    /// Returns `true` if the allocation is zero-sized.
+    pub fn is_empty(&self) -> bool {
+        self.layout.size() == 0
+    }
+    /// Returns `true` if the allocation is zero-sized, same as [`Self::is_empty`].
+    ///
+    /// A zero-sized allocation is usually backed by a dangling pointer and does not own any memory. All accessors
+    /// return empty views and reallocating it allocates fresh memory.
+    pub fn is_zero_sized(&self) -> bool {
+        self.layout.size() == 0
+    }
+    /// The size of the allocation in bytes, i.e. `self.layout().size()`.
+    pub fn size(&self) -> usize {
+        self.layout.size()
+    }
+    /// The alignment of the allocation, i.e. `self.layout().align()`.
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+    /// The capacity of the allocation in bytes, i.e. `self.layout().size()`.
+    pub fn byte_capacity(&self) -> usize {
+        self.layout.size()
+    }
+    /// The number of `T` that fit into the allocation.
+    ///
+    /// Returns `0` if `T` is zero-sized or the allocation is not aligned enough for a `T`.
+    /// Note that [`try_into_vec`](Self::try_into_vec) additionally requires the allocation to have exactly
+    /// the alignment of `T` and no slack bytes.
+    pub fn capacity_for<T>(&self) -> usize {
+        if size_of::<T>() == 0 || self.layout.align() < align_of::<T>() {
+            return 0;
+        }
+        self.layout.size() / size_of::<T>()
+    }
+}
+/// Methods using the allocator-api or shim
+impl<A: Allocator> Allocation<A> {
+    /// Allocate new memory for the given layout in a given allocator.
+    ///
+    /// The pointer backing the allocation is valid for reads and writes of `layout.size()` bytes and this
+    /// memory region does not alias any other existing allocation.
+    ///
+    /// The pointer is guaranteed to be aligned to `layout.align()` but several systems align memory more
+    /// lax when a small alignment is requested.
+    ///
+    /// Memory is not initialized or zeroed, try [`Self::zeroed_in`] instead.
+    ///
+    /// The allocator is moved into the allocation. To share an allocator between several allocations, pass
+    /// a reference, for example with [`Allocator::by_ref`]:
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly-std-conversions", feature(allocator_api))]
+    /// # use core::alloc::Layout;
+    /// # use allocator_api2::alloc::{Allocator, Global};
+    /// # use untyped_box::{Allocation, TrackingAllocator};
+    /// let tracking = TrackingAllocator::new(Global);
+    /// let first = Allocation::new_in(Layout::new::<u32>(), tracking.by_ref());
+    /// let second = Allocation::new_in(Layout::new::<u64>(), tracking.by_ref());
+    /// assert_eq!(tracking.live_allocations(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    /// See [`Self::try_new_in`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_in(layout: Layout, alloc: A) -> Self {
+        Self::try_new_in(layout, alloc)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout))
+    }
+    /// Allocate new memory for the given layout in a given allocator.
+    ///
+    /// Returns an error when no memory could be allocated.
+    pub fn try_new_in(layout: Layout, alloc: A) -> Result<Self, AllocError> {
+        let requested = layout;
+        let (ptr, layout) = allocate(&alloc, layout)?;
+        Ok(Self {
+            ptr,
+            layout,
+            requested,
+            alloc,
+            #[cfg(feature = "generation")]
+            generation: 0,
+        })
+    }
+    /// Allocate new zeroed-out memory for the given layout in a given allocator.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    /// See [`Self::try_zeroed_in`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn zeroed_in(layout: Layout, alloc: A) -> Self {
+        Self::try_zeroed_in(layout, alloc)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout))
+    }
+    /// Allocate new zeroed-out memory for the given layout in a given allocator.
+    ///
+    /// Returns an error when no memory could be allocated.
+    pub fn try_zeroed_in(layout: Layout, alloc: A) -> Result<Self, AllocError> {
+        let requested = layout;
+        let (ptr, layout) = allocate_zeroed(&alloc, layout)?;
+        Ok(Self {
+            ptr,
+            layout,
+            requested,
+            alloc,
+            #[cfg(feature = "generation")]
+            generation: 0,
+        })
+    }
+    /// Allocate new memory for a `T` in a given allocator and move `value` into it.
+    ///
+    /// See [`Self::from_value`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_value_in<T>(value: T, alloc: A) -> Self {
+        let mut this = Self::new_in(Layout::new::<T>(), alloc);
+        this.write_value(value);
+        this
+    }
+    /// Allocate new memory for a `T` in a given allocator.
+    ///
+    /// See [`Self::new_for`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_for_in<T>(alloc: A) -> Self {
+        Self::new_in(Layout::new::<T>(), alloc)
+    }
+    /// Allocate new zeroed-out memory for a `T` in a given allocator.
+    ///
+    /// See [`Self::zeroed_for`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn zeroed_for_in<T>(alloc: A) -> Self {
+        Self::zeroed_in(Layout::new::<T>(), alloc)
+    }
+    /// Allocate new memory for `cap` bytes with an alignment of `1` in a given allocator.
+    ///
+    /// See [`Self::with_capacity_bytes`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn with_capacity_bytes_in(cap: usize, alloc: A) -> Self {
+        let layout = Layout::array::<u8>(cap).expect("capacity overflow");
+        Self::new_in(layout, alloc)
+    }
+    /// Allocate new memory for an array of `n` elements of type `T` in a given allocator.
+    ///
+    /// See [`Self::new_array`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_array_in<T>(n: usize, alloc: A) -> Self {
+        Self::new_array_checked_in::<T>(n, alloc).expect("capacity overflow")
+    }
+    /// Allocate new memory for an array of `n` elements of type `T` in a given allocator.
+    ///
+    /// See [`Self::new_array_checked`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_array_checked_in<T>(n: usize, alloc: A) -> Result<Self, LayoutError> {
+        Ok(Self::new_in(Layout::array::<T>(n)?, alloc))
+    }
+    /// Allocate new zeroed-out memory for an array of `n` elements of type `T` in a given allocator.
+    ///
+    /// See [`Self::zeroed_array`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn zeroed_array_in<T>(n: usize, alloc: A) -> Self {
+        Self::try_zeroed_array_in::<T>(n, alloc).expect("capacity overflow")
+    }
+    /// Allocate new zeroed-out memory for an array of `n` elements of type `T` in a given allocator.
+    ///
+    /// See [`Self::try_zeroed_array`] for more details.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn try_zeroed_array_in<T>(n: usize, alloc: A) -> Result<Self, LayoutError> {
+        Ok(Self::zeroed_in(Layout::array::<T>(n)?, alloc))
+    }
+    /// Allocate new memory in a given allocator and copy the bytes of `src` into it.
+    ///
+    /// See [`Self::from_slice`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_slice_in(src: &[u8], alloc: A) -> Self {
+        Self::try_from_slice_in(src, alloc).unwrap_or_else(|AllocError| {
+            crate::alloc_shim::handle_alloc_error(Layout::for_value(src))
+        })
+    }
+    /// Allocate new memory in a given allocator and copy the bytes of `src` into it.
+    ///
+    /// Same as [`Self::from_slice_in`] but returns an error when memory could not be allocated.
+    pub fn try_from_slice_in(src: &[u8], alloc: A) -> Result<Self, AllocError> {
+        let mut this = Self::try_new_in(Layout::for_value(src), alloc)?;
+        this.write_slice(src);
+        Ok(this)
+    }
+    /// Allocate new memory fitting the layout of `value` in a given allocator.
+    ///
+    /// See [`Self::for_value`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn for_value_in<T: ?Sized>(value: &T, alloc: A) -> Self {
+        Self::new_in(Layout::for_value(value), alloc)
+    }
+    /// Split the allocation into its raw parts including the allocator.
+    ///
+    /// Deallocating the allocation is the responsibility of the caller. The returned
+    /// pointer can be passed to `alloc.deallocate()`.
+    ///
+    /// The returned layout describes the whole memory block, which might be larger than requested. The memory also
+    /// [fits](Allocator#memory-fitting) the [requested layout](Self::requested_layout), so it can be deallocated with
+    /// that layout instead. For example, memory allocated for `Layout::new::<T>()` can always be handed to a `Box<T>`,
+    /// even if the allocator returned a larger block.
+    pub fn into_parts_with_alloc(self) -> (NonNull<u8>, Layout, A) {
+        let me = core::mem::ManuallyDrop::new(self);
+        let alloc = unsafe { core::ptr::read(&me.alloc) };
+        (me.ptr.get(), me.layout, alloc)
+    }
+    /// Consumes and leaks the allocation, returning a mutable reference to its bytes.
+    ///
+    /// The memory, as well as the allocator, is never dropped. The returned reference stays valid for as long
+    /// as the allocator is alive, for example when it is itself borrowed for `'a`.
+    pub fn leak_in<'a>(self) -> &'a mut [MaybeUninit<u8>]
+    where
+        A: 'a,
+    {
+        let me = core::mem::ManuallyDrop::new(self);
+        unsafe { core::slice::from_raw_parts_mut(me.ptr.get().as_ptr().cast(), me.layout.size()) }
+    }
+    /// Constructs an [`Allocation`] from a pointer and layout information in the given allocator.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to [*currently-allocated*] memory from the given allocator, and `layout`
+    /// [*fits*] that memory.
+    ///
+    /// [*currently-allocated*]: Allocator#currently-allocated-memory
+    /// [*fits*]: Allocator#memory-fitting
+    pub unsafe fn from_parts_in(ptr: NonNull<u8>, layout: Layout, alloc: A) -> Self {
+        Self {
+            ptr: Unique(ptr),
+            layout,
+            requested: layout,
+            alloc,
+            #[cfg(feature = "generation")]
+            generation: 0,
+        }
+    }
+    /// Constructs an [`Allocation`] from a memory block as returned by an [`Allocator`].
+    ///
+    /// The layout is derived from the length of `slice` and `align`. The counterpart of this is
+    /// [`Self::into_raw_nonnull_slice`].
+    ///
+    /// # Safety
+    ///
+    /// The slice must point to [*currently-allocated*] memory from the given allocator, and the layout
+    /// with size `slice.len()` and alignment `align` [*fits*] that memory.
+    ///
+    /// [*currently-allocated*]: Allocator#currently-allocated-memory
+    /// [*fits*]: Allocator#memory-fitting
+    ///
+    /// # Panics
+    ///
+    /// If `align` is not a power of two or the size overflows, see [`Layout::from_size_align`].
+    pub unsafe fn from_raw_slice_in(slice: NonNull<[u8]>, align: usize, alloc: A) -> Self {
+        let layout = Layout::from_size_align(slice.len(), align)
+            .expect("invalid layout for the memory block");
+        unsafe { Self::from_parts_in(slice.cast(), layout, alloc) }
+    }
+    /// Split the allocation into the memory block and the allocator.
+    ///
+    /// The length of the returned slice is `self.layout().size()`. Deallocating the memory is the responsibility of
+    /// the caller, see [`Self::into_parts_with_alloc`].
+    pub fn into_raw_nonnull_slice(self) -> (NonNull<[u8]>, A) {
+        let (ptr, layout, alloc) = self.into_parts_with_alloc();
+        (NonNull::slice_from_raw_parts(ptr, layout.size()), alloc)
+    }
+    /// Constructs an [`Allocation`] from a pointer obtained from C code.
+    ///
+    /// See [`Self::from_parts_in`], the counterpart of this is [`Self::as_c_void`].
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to [*currently-allocated*] memory from the given allocator, and `layout`
+    /// [*fits*] that memory.
+    ///
+    /// [*currently-allocated*]: Allocator#currently-allocated-memory
+    /// [*fits*]: Allocator#memory-fitting
+    pub unsafe fn from_c_void(ptr: *mut c_void, layout: Layout, alloc: A) -> Self {
+        let ptr = unsafe { NonNull::new_unchecked(ptr.cast()) };
+        unsafe { Self::from_parts_in(ptr, layout, alloc) }
+    }
+    /// Moves the allocation into another allocator.
+    ///
+    /// Allocates memory with the same layout in `dst`, copies the contents over and deallocates the original memory.
+    /// Returns an error when no memory could be allocated in `dst`. In this case, `self` is dropped.
+    pub fn copy_to<B: Allocator>(self, dst: B) -> Result<Allocation<B>, AllocError> {
+        self.try_clone_in(dst)
+    }
+    /// Copies the allocation into another allocator.
+    ///
+    /// Allocates memory with the same layout in `alloc` and copies the contents over, leaving `self` intact.
+    /// Returns an error when no memory could be allocated.
+    pub fn try_clone_in<B: Allocator>(&self, alloc: B) -> Result<Allocation<B>, AllocError> {
+        let mut copy = Allocation::try_new_in(self.layout, alloc)?;
+        if self.layout.size() != 0 {
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    self.ptr.get().as_ptr(),
+                    copy.ptr.get().as_ptr(),
+                    self.layout.size(),
+                )
+            };
+        }
+        copy.requested = self.requested;
+        Ok(copy)
+    }
+    // Moves the allocation out, leaving behind a zero-sized allocation in a clone of the allocator.
+    #[cfg(not(no_global_oom_handling))]
+    pub(crate) fn take(&mut self) -> Self
+    where
+        A: Clone,
+    {
+        let placeholder = Self::new_in(Layout::new::<()>(), self.alloc.clone());
+        core::mem::replace(self, placeholder)
+    }
+    /// Splits the allocation into two allocations of `mid` and `size - mid` bytes.
+    ///
+    /// This is a copying split: the halves are copied into two new allocations and the original memory is deallocated
+    /// afterwards, since an allocator can in general not deallocate only a part of a memory block. Both halves have the
+    /// alignment of the original allocation.
+    ///
+    /// Returns `self` unchanged when no memory could be allocated.
+    ///
+    /// # Panics
+    ///
+    /// If `mid` is larger than the size of the allocation.
+    pub fn split_at(self, mid: usize) -> Result<(Self, Self), Self>
+    where
+        A: Clone,
+    {
+        let size = self.layout.size();
+        assert!(mid <= size, "mid exceeds the allocation size");
+        let align = self.layout.align();
+        let Ok(first) = Self::try_new_in(
+            Layout::from_size_align(mid, align).unwrap(),
+            self.alloc.clone(),
+        ) else {
+            return Err(self);
+        };
+        let Ok(second) = Self::try_new_in(
+            Layout::from_size_align(size - mid, align).unwrap(),
+            self.alloc.clone(),
+        ) else {
+            return Err(self);
+        };
+        let src = self.ptr.get().as_ptr();
+        unsafe {
+            core::ptr::copy_nonoverlapping(src, first.ptr.get().as_ptr(), mid);
+            core::ptr::copy_nonoverlapping(src.add(mid), second.ptr.get().as_ptr(), size - mid);
+        }
+        Ok((first, second))
+    }
+    /// Appends the contents of `other` to the end of this allocation.
+    ///
+    /// The allocation is grown to `self.len() + other.len()` bytes with the larger alignment of both allocations,
+    /// `other` is copied after the existing bytes and then deallocated.
+    ///
+    /// Returns `other` when the memory could not be reallocated. In this case, `self` is left unchanged.
+    pub fn append(&mut self, other: Self) -> Result<(), Self> {
+        let offset = self.layout.size();
+        let Some(new_size) = offset.checked_add(other.layout.size()) else {
+            return Err(other);
+        };
+        let align = self.layout.align().max(other.layout.align());
+        let Ok(new_layout) = Layout::from_size_align(new_size, align) else {
+            return Err(other);
+        };
+        if self.try_realloc(new_layout).is_err() {
+            return Err(other);
+        }
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                other.ptr.get().as_ptr(),
+                self.ptr.get().as_ptr().add(offset),
+                other.layout.size(),
+            )
+        };
+        Ok(())
+    }
+    /// Moves the allocation into the global allocator, see [`Self::copy_to`].
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_global(self) -> Allocation {
+        let layout = self.layout;
+        self.copy_to(Global)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout))
+    }
+    /// Moves an allocation from the global allocator into the given allocator, see [`Self::copy_to`].
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_global_in(allocation: Allocation, alloc: A) -> Self {
+        let layout = allocation.layout;
+        allocation
+            .copy_to(alloc)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout))
+    }
+    /// Replaces the allocation with `new`, returning the previous one.
+    ///
+    /// This is the same as [`mem::replace`](core::mem::replace), named for use cases such as double-buffering.
+    #[inline]
+    pub fn replace_with(&mut self, new: Self) -> Self {
+        core::mem::replace(self, new)
+    }
+    /// Replaces the memory block with a fresh allocation of the given layout from the same allocator.
+    ///
+    /// In contrast to [`realloc`](Self::realloc), the contents are not preserved and the new block is always
+    /// allocated. The old block is deallocated once the new one has been allocated.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic. In this case, pointers are still valid.
+    /// See [`Self::try_reset_to`] for a version that returns an error instead.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reset_to(&mut self, layout: Layout) {
+        let () = self
+            .try_reset_to(layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout));
+    }
+    /// Replaces the memory block with a fresh allocation of the given layout from the same allocator.
+    ///
+    /// Returns an error when no memory could be allocated. In this case, the allocation is unchanged.
+    ///
+    /// # See also
+    ///
+    /// [`Self::reset_to`] for more details.
+    pub fn try_reset_to(&mut self, layout: Layout) -> Result<(), AllocError> {
+        let (ptr, new_layout) = allocate(&self.alloc, layout)?;
+        unsafe { self.alloc.deallocate(self.ptr.get(), self.layout) };
+        (self.ptr, self.layout) = (ptr, new_layout);
+        self.requested = layout;
+        self.bump_generation();
+        Ok(())
+    }
+    /// Reallocates memory to a new layout.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # See also
     ///
     /// [`Self::realloc`] for more disuccion about the memory contents after reallocation.
     pub fn try_realloc(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        if new_layout.size() == 0 {
+            return self.try_realloc_to_zero(new_layout);
+        }
+        // The allocator might have given us more memory than requested. Avoid a surprising reallocation
+        // if the current block already satisfies the request.
+        if self.fits_layout(new_layout) {
+            self.requested = new_layout;
+            return Ok(());
+        }
+        self.try_reallocate(new_layout)
+    }
+    /// Reallocates memory to a new layout and reports whether the allocation moved.
+    ///
+    /// Same as [`try_realloc`](Self::try_realloc), but returns `Ok(true)` if the allocation is now backed by a
+    /// different pointer, and `Ok(false)` if it stayed in place, e.g. because the current block already fit.
+    pub fn try_realloc_moved(&mut self, new_layout: Layout) -> Result<bool, AllocError> {
+        let old_ptr = self.ptr.get();
+        self.try_realloc(new_layout)?;
+        Ok(self.ptr.get() != old_ptr)
+    }
+    /// Reallocates memory to a new layout and reports whether it happened in place.
+    ///
+    /// This is [`try_realloc_moved`](Self::try_realloc_moved), with the result mapped onto [`Reallocation`]. In
+    /// particular, if the current block already [fits](Self::fits_layout) the new layout, it is kept and
+    /// [`Reallocation::InPlace`] is returned. Otherwise, the allocator is asked to reallocate the block, which may
+    /// still extend it in place or fall back to allocating a new block, copying the contents and freeing the old one.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    pub fn try_realloc_in_place_or_copy(
+        &mut self,
+        new_layout: Layout,
+    ) -> Result<Reallocation, AllocError> {
+        if self.try_realloc_moved(new_layout)? {
+            Ok(Reallocation::Moved)
+        } else {
+            Ok(Reallocation::InPlace)
+        }
+    }
+    /// Reallocates memory to exactly the new layout.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # See also
+    ///
+    /// [`Self::realloc_exact`] for the difference to [`Self::try_realloc`].
+    pub fn try_realloc_exact(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        self.try_reallocate(new_layout)
+    }
+    // Reallocates to exactly the new layout, even if the current block already fits it.
+    pub(crate) fn try_reallocate(&mut self, new_layout: Layout) -> Result<(), AllocError> {
         if new_layout == self.layout {
+            self.requested = new_layout;
             return Ok(());
         }
+        if new_layout.size() == 0 {
+            return self.try_realloc_to_zero(new_layout);
+        }
         // Prefer grow to shrink when all we do is change alignment
         if new_layout.size() >= self.layout.size() {
-            (self.ptr, self.layout) =
-                unsafe { grow(&self.alloc, self.ptr, self.layout, new_layout)? };
-            Ok(())
+            self.try_grow(new_layout)
         } else {
-            (self.ptr, self.layout) =
-                unsafe { shrink(&self.alloc, self.ptr, self.layout, new_layout)? };
-            Ok(())
+            self.try_shrink(new_layout)
+        }
+    }
+    /// Reserves room for at least `additional` more bytes after the [requested size](Self::requested_layout).
+    ///
+    /// Returns an error when the memory could not be reallocated or the new size overflows. In this case, any
+    /// previously derived pointers remain valid and no memory is deallocated.
+    ///
+    /// # See also
+    ///
+    /// [`Self::reserve`] for more discussion about the growth strategy.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let (requested, amortized) = self.reserve_layouts(additional).ok_or(AllocError)?;
+        if !self.fits_layout(requested) {
+            self.try_grow(amortized)?;
+        }
+        self.requested = requested;
+        Ok(())
+    }
+    // Computes the requested layout after reserving `additional` bytes and the layout to grow to if the current
+    // block does not fit it. Grows by at least doubling the current block to amortize the cost of reallocating.
+    fn reserve_layouts(&self, additional: usize) -> Option<(Layout, Layout)> {
+        let align = self.requested.align();
+        let needed = self.requested.size().checked_add(additional)?;
+        let requested = Layout::from_size_align(needed, align).ok()?;
+        let doubled = needed.max(self.layout.size().saturating_mul(2));
+        let amortized = Layout::from_size_align(doubled, align).unwrap_or(requested);
+        Some((requested, amortized))
+    }
+    /// Grows the allocation to a new layout without moving it.
+    ///
+    /// The [`Allocator`] trait has no way to grow a memory block in place. Hence, this only succeeds if the current
+    /// memory block already [fits](Self::fits_layout) the new layout, for example because the allocator handed out more
+    /// memory than requested. Pointers into the allocation stay valid.
+    ///
+    /// Returns an error if the layout does not fit. In this case, the allocation is unchanged.
+    pub fn try_grow_in_place(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        if !self.fits_layout(new_layout) {
+            return Err(AllocError);
+        }
+        self.requested = new_layout;
+        Ok(())
+    }
+    /// Shrinks the memory block to the [requested layout](Self::requested_layout).
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # See also
+    ///
+    /// [`Self::shrink_to_fit`] for more discussion.
+    pub fn try_shrink_to_fit(&mut self) -> Result<(), AllocError> {
+        if self.layout.size() > self.requested.size() {
+            self.try_shrink(self.requested)?;
+        }
+        Ok(())
+    }
+    /// Shrinks the memory block to at least `min_capacity_bytes`, keeping the alignment.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # See also
+    ///
+    /// [`Self::shrink_to`] for more discussion.
+    pub fn try_shrink_to(&mut self, min_capacity_bytes: usize) -> Result<(), AllocError> {
+        let target = min_capacity_bytes.max(self.requested.size());
+        if self.layout.size() > target {
+            let requested = self.requested;
+            // The target lies between the requested size and the current size, both valid for this alignment.
+            let new_layout =
+                unsafe { Layout::from_size_align_unchecked(target, self.layout.align()) };
+            self.try_shrink(new_layout)?;
+            self.requested = requested;
         }
+        Ok(())
+    }
+    /// Grows the allocation to fit `additional` more elements of type `T`.
+    ///
+    /// The current element capacity is the number of `T` that fit into [`Self::layout`], even if the allocation is not
+    /// yet aligned for a `T`. The allocation is reallocated to `Layout::array::<T>(capacity + additional)`, keeping the
+    /// alignment of the allocation if it is stricter than that of `T`. See [`try_realloc`](Self::try_realloc).
+    ///
+    /// Returns an error if `T` is zero-sized, if the new capacity overflows, if reallocating would shrink the memory
+    /// block, or when the memory could not be reallocated. In this case, any previously derived pointers remain valid
+    /// and no memory is deallocated.
+    pub fn try_grow_elements<T>(&mut self, additional: usize) -> Result<(), AllocError> {
+        // An array of zero-sized elements needs no memory, "growing" to it would free the block.
+        if size_of::<T>() == 0 {
+            return Err(AllocError);
+        }
+        let capacity = (self.layout.size() / size_of::<T>())
+            .checked_add(additional)
+            .ok_or(AllocError)?;
+        let new_layout = Layout::array::<T>(capacity)
+            .and_then(|layout| layout.align_to(self.layout.align()))
+            .map_err(|_| AllocError)?;
+        // Slack bytes that do not fit a whole `T` would be lost when moving to a more strictly aligned block.
+        if !self.fits_layout(new_layout) && new_layout.size() < self.layout.size() {
+            return Err(AllocError);
+        }
+        self.try_realloc(new_layout)
+    }
+    /// Grows the allocation to a new layout.
+    ///
+    /// In contrast to [`try_realloc`](Self::try_realloc), this always calls [`Allocator::grow`], even if the current block
+    /// already fits the new layout. Existing (possibly uninitialized) bytes are preserved, newly allocated bytes are uninitialized.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is smaller than [`Self::layout`].
+    pub fn try_grow(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        assert!(
+            new_layout.size() >= self.layout.size(),
+            "new layout must not be smaller than the current layout"
+        );
+        (self.ptr, self.layout) =
+            unsafe { grow(&self.alloc, self.ptr.get(), self.layout, new_layout)? };
+        self.requested = new_layout;
+        self.bump_generation();
+        Ok(())
+    }
+    /// Grows the allocation to a new layout, zeroing the newly allocated bytes.
+    ///
+    /// This always calls [`Allocator::grow_zeroed`], see [`try_grow`](Self::try_grow). Existing (possibly uninitialized)
+    /// bytes are preserved.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is smaller than [`Self::layout`].
+    pub fn try_grow_zeroed(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        assert!(
+            new_layout.size() >= self.layout.size(),
+            "new layout must not be smaller than the current layout"
+        );
+        (self.ptr, self.layout) =
+            unsafe { grow_zeroed(&self.alloc, self.ptr.get(), self.layout, new_layout)? };
+        self.requested = new_layout;
+        self.bump_generation();
+        Ok(())
+    }
+    /// Shrinks the allocation to a new layout.
+    ///
+    /// In contrast to [`try_realloc`](Self::try_realloc), this always calls [`Allocator::shrink`], even if the current block
+    /// already fits the new layout. The first `new_layout.size()` bytes are preserved.
+    ///
+    /// Returns an error when the memory could not be reallocated. In this case, any previously derived
+    /// pointers remain valid and no memory is deallocated.
+    ///
+    /// # Panics
+    ///
+    /// If `new_layout` is larger than [`Self::layout`].
+    pub fn try_shrink(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        assert!(
+            new_layout.size() <= self.layout.size(),
+            "new layout must not be larger than the current layout"
+        );
+        if new_layout.size() == 0 {
+            return self.try_realloc_to_zero(new_layout);
+        }
+        (self.ptr, self.layout) =
+            unsafe { shrink(&self.alloc, self.ptr.get(), self.layout, new_layout)? };
+        self.requested = new_layout;
+        self.bump_generation();
+        Ok(())
     }
     /// Reallocates memory to a new layout.
     ///
@@ -333,29 +1678,91 @@ impl<A: Allocator> Allocation<A> {
     ///
     /// [`Self::realloc_zeroed`] for more disuccion about the memory contents after reallocation.
     pub fn try_realloc_zeroed(&mut self, new_layout: Layout) -> Result<(), AllocError> {
-        if new_layout == self.layout {
-            return Ok(());
+        if new_layout.size() == 0 {
+            return self.try_realloc_to_zero(new_layout);
         }
-        // Prefer grow to shrink when all we do is change alignment
-        if new_layout.size() >= self.layout.size() {
-            (self.ptr, self.layout) =
-                unsafe { grow_zeroed(&self.alloc, self.ptr, self.layout, new_layout)? };
-            Ok(())
+        // The allocator only zeroes memory past the current block. Bytes of the block past the requested size
+        // are preserved instead and have to be zeroed by hand.
+        let stale = self.requested.size()..new_layout.size().min(self.layout.size());
+        if self.fits_layout(new_layout) {
+            self.requested = new_layout;
         } else {
-            (self.ptr, self.layout) =
-                unsafe { shrink(&self.alloc, self.ptr, self.layout, new_layout)? };
-            Ok(())
+            // Prefer grow to shrink when all we do is change alignment
+            if new_layout.size() >= self.layout.size() {
+                (self.ptr, self.layout) =
+                    unsafe { grow_zeroed(&self.alloc, self.ptr.get(), self.layout, new_layout)? };
+            } else {
+                (self.ptr, self.layout) =
+                    unsafe { shrink(&self.alloc, self.ptr.get(), self.layout, new_layout)? };
+            }
+            self.requested = new_layout;
+            self.bump_generation();
+        }
+        if !stale.is_empty() {
+            unsafe { self.ptr.get().add(stale.start).write_bytes(0, stale.len()) };
         }
+        Ok(())
+    }
+    // Not all allocators support shrinking to a size of zero. Instead, release the old block and replace it with a
+    // fresh zero-sized allocation, which is usually just a dangling pointer for the new alignment.
+    fn try_realloc_to_zero(&mut self, new_layout: Layout) -> Result<(), AllocError> {
+        debug_assert_eq!(new_layout.size(), 0);
+        if new_layout == self.layout {
+            self.requested = new_layout;
+            return Ok(());
+        }
+        let (ptr, layout) = allocate(&self.alloc, new_layout)?;
+        unsafe { self.alloc.deallocate(self.ptr.get(), self.layout) };
+        (self.ptr, self.layout) = (ptr, layout);
+        self.requested = new_layout;
+        self.bump_generation();
+        Ok(())
+    }
+}
+
+/// The default allocation is zero-sized with an alignment of `1` and owns no heap memory.
+impl Default for Allocation {
+    fn default() -> Self {
+        Self::dangling::<u8>()
+    }
+}
+
+impl<A: Allocator> AsRef<[MaybeUninit<u8>]> for Allocation<A> {
+    fn as_ref(&self) -> &[MaybeUninit<u8>] {
+        self.as_bytes()
+    }
+}
+
+impl<A: Allocator> Borrow<[MaybeUninit<u8>]> for Allocation<A> {
+    fn borrow(&self) -> &[MaybeUninit<u8>] {
+        self.as_bytes()
+    }
+}
+
+impl<A: Allocator> AsMut<[MaybeUninit<u8>]> for Allocation<A> {
+    fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        self.as_bytes_mut()
+    }
+}
+
+impl<A: Allocator, I: SliceIndex<[MaybeUninit<u8>]>> Index<I> for Allocation<A> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.as_bytes()[index]
+    }
+}
+
+impl<A: Allocator, I: SliceIndex<[MaybeUninit<u8>]>> IndexMut<I> for Allocation<A> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.as_bytes_mut()[index]
     }
 }
 
 impl<A: Allocator> Drop for Allocation<A> {
     fn drop(&mut self) {
         unsafe {
-            self.alloc.deallocate(self.ptr, self.layout);
+            self.alloc.deallocate(self.ptr.get(), self.layout);
         }
     }
 }
-
-unsafe impl<A: Allocator + Sync> Sync for Allocation<A> {}
-unsafe impl<A: Allocator + Send> Send for Allocation<A> {}
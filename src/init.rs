@@ -0,0 +1,111 @@
+#[cfg(not(no_global_oom_handling))]
+use core::alloc::Layout;
+
+use alloc::vec::Vec;
+
+use crate::{
+    alloc_shim::{Allocator, Global},
+    Allocation, VecConversionError,
+};
+
+/// An allocation that tracks how many bytes at its start are initialized.
+///
+/// Bytes are appended with [`push_bytes`](Self::push_bytes), which grows the allocation as needed. The initialized
+/// prefix can then be viewed with [`as_init_slice`](Self::as_init_slice) or converted into a [`Vec`] of the matching
+/// length with [`into_vec`](Self::into_vec).
+pub struct InitAllocation<A: Allocator = Global> {
+    inner: Allocation<A>,
+    init: usize,
+}
+
+impl InitAllocation {
+    /// Allocate new memory for the given layout, with no bytes initialized.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new(layout: Layout) -> Self {
+        Self::new_in(layout, Global)
+    }
+    /// Convert into a [`Vec`] whose length covers the initialized bytes.
+    ///
+    /// This fails under the same conditions as [`Allocation::try_into_vec`], or if the number of initialized bytes
+    /// is not a multiple of the size of `T`.
+    ///
+    /// # Safety
+    ///
+    /// The initialized bytes must form valid values of type `T`.
+    pub unsafe fn into_vec<T>(self) -> Result<Vec<T>, VecConversionError> {
+        let element_size = size_of::<T>();
+        #[allow(clippy::manual_is_multiple_of)] // would require MSRV of 1.87
+        if element_size != 0 && self.init % element_size != 0 {
+            return Err(VecConversionError::SlackCapacity {
+                element_size,
+                allocated: self.init,
+            });
+        }
+        let len = self.init.checked_div(element_size).unwrap_or(0);
+        unsafe { self.inner.into_vec_with_len(len) }
+    }
+}
+
+impl<A: Allocator> InitAllocation<A> {
+    /// Allocate new memory for the given layout in a given allocator, with no bytes initialized.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_in(layout: Layout, alloc: A) -> Self {
+        Self::from(Allocation::new_in(layout, alloc))
+    }
+    /// The number of initialized bytes.
+    pub fn len(&self) -> usize {
+        self.init
+    }
+    /// Returns `true` if no bytes are initialized.
+    pub fn is_empty(&self) -> bool {
+        self.init == 0
+    }
+    /// Append bytes after the initialized prefix, growing the allocation if necessary.
+    ///
+    /// # Panics
+    ///
+    /// If the new size overflows, see [`Allocation::reserve`].
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        let needed = self
+            .init
+            .checked_add(bytes.len())
+            .expect("capacity overflow");
+        if needed > self.inner.len() {
+            self.inner.reserve(needed - self.inner.requested_size());
+        }
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.inner.as_ptr::<u8>().as_ptr().add(self.init),
+                bytes.len(),
+            )
+        };
+        self.init = needed;
+    }
+    /// View the initialized bytes.
+    pub fn as_init_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.inner.as_ptr::<u8>().as_ptr(), self.init) }
+    }
+    /// Get the underlying allocation, forgetting which bytes are initialized.
+    pub fn into_inner(self) -> Allocation<A> {
+        self.inner
+    }
+}
+
+/// Adopts the allocation with no bytes initialized.
+impl<A: Allocator> From<Allocation<A>> for InitAllocation<A> {
+    fn from(inner: Allocation<A>) -> Self {
+        Self { inner, init: 0 }
+    }
+}
@@ -1,6 +1,13 @@
 #![doc = include_str!("../README.md")]
 //! ## Available features
 //! - `nightly-std-conversions`: Requires nightly and enables additional conversions for `Box` and `Vec` types in std.
+//! - `libc`: Enables the [`Malloc`] allocator, backed by the C allocator.
+//! - `allocator-api2`: Enables conversions to and from the `Box` and `Vec` types of `allocator-api2` for any allocator on stable.
+//! - `bytemuck`: Enables typed views of the allocation for `Pod` and `Zeroable` types of `bytemuck`.
+//! - `zerocopy`: Enables typed views of the allocation for `FromBytes` types of `zerocopy`.
+//! - `bytes`: Enables conversions to and from `BytesMut` of `bytes`.
+//! - `stable_deref`: Implements `StableDeref` of `stable_deref_trait` for [`AllocationBytes`].
+//! - `generation`: Tracks a [generation counter](Allocation::generation) in every allocation to detect stale pointers.
 #![no_std]
 #![cfg_attr(feature = "nightly-std-conversions", feature(allocator_api))]
 #![warn(missing_docs)]
@@ -10,9 +17,42 @@ extern crate alloc;
 mod alloc_shim;
 
 mod r#impl;
-pub use r#impl::Allocation;
+pub use r#impl::{Allocation, Reallocation};
+mod batch;
+pub use batch::BatchResize;
+mod alloc_util;
+pub use alloc_util::{CacheAligned, TrackingAllocator};
+mod arena;
+pub use arena::Bump;
+mod secure;
+pub use secure::SecureAllocation;
+mod init;
+pub use init::InitAllocation;
+mod typed;
+pub use typed::TypedAllocation;
+mod bytes_deref;
+pub use bytes_deref::AllocationBytes;
+mod byte_view;
+pub use byte_view::ByteView;
+mod c_alloc;
+mod fn_alloc;
+pub use fn_alloc::{AllocFn, FnAllocator, FreeFn, ReallocFn};
+#[cfg(feature = "libc")]
+mod malloc;
+#[cfg(feature = "libc")]
+pub use malloc::Malloc;
 mod std_conversions;
-pub use std_conversions::{BoxConversionError, VecConversionError};
+pub use std_conversions::{BoxConversionError, ConversionError, VecConversionError};
+#[cfg(feature = "allocator-api2")]
+mod api2_conversions;
+#[cfg(feature = "bytes")]
+mod bytes_mut;
+#[cfg(feature = "zerocopy")]
+mod from_bytes;
+#[cfg(feature = "bytemuck")]
+mod pod;
 
-#[cfg(test)]
+#[cfg(all(test, not(no_global_oom_handling)))]
 mod test;
+#[cfg(test)]
+mod test_fallible;
@@ -0,0 +1,116 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::{
+    alloc_shim::{AllocError, Allocator},
+    c_alloc,
+};
+
+/// An allocator using the C allocator, i.e. `malloc`, `realloc` and `free`.
+///
+/// This allows to adopt memory allocated by C code into an [`Allocation`](crate::Allocation), and hand
+/// memory back to C code that frees it with `free`:
+///
+/// ```
+/// # use core::{alloc::Layout, ptr::NonNull};
+/// # use untyped_box::{Allocation, Malloc};
+/// let layout = Layout::new::<[u32; 4]>();
+/// let ptr = unsafe { libc::malloc(layout.size()) };
+/// let ptr = NonNull::new(ptr.cast()).expect("out of memory");
+/// let alloc = unsafe { Allocation::from_parts_in(ptr, layout, Malloc) };
+/// // `alloc` calls `free` on drop
+/// ```
+///
+/// # Alignment
+///
+/// `malloc` only guarantees memory suitably aligned for any object of the requested size, that is to the alignment
+/// of `max_align_t` if the size is large enough. Requests for a stricter alignment, or an alignment larger than the
+/// size, are served by `posix_memalign` instead. Memory allocated this way can still be freed with `free`. On targets
+/// without `posix_memalign`, such requests fail with an [`AllocError`].
+///
+/// Zero-sized allocations do not call into the C allocator and use a dangling pointer instead.
+/// Adopting memory from C with a zero-sized layout will hence leak that memory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Malloc;
+
+impl Malloc {
+    const MAX_ALIGN: usize = align_of::<libc::max_align_t>();
+
+    #[cfg(unix)]
+    fn aligned_alloc(layout: Layout) -> *mut libc::c_void {
+        let mut ptr = core::ptr::null_mut();
+        // `posix_memalign` requires the alignment to be a multiple of the size of a pointer.
+        let align = layout.align().max(size_of::<usize>());
+        match unsafe { libc::posix_memalign(&mut ptr, align, layout.size()) } {
+            0 => ptr,
+            _ => core::ptr::null_mut(),
+        }
+    }
+    #[cfg(not(unix))]
+    fn aligned_alloc(_layout: Layout) -> *mut libc::c_void {
+        core::ptr::null_mut()
+    }
+    unsafe fn realloc(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            c_alloc::reallocate(
+                self,
+                ptr,
+                old_layout,
+                new_layout,
+                Self::MAX_ALIGN,
+                |ptr, size| libc::realloc(ptr, size),
+            )
+        }
+    }
+}
+
+unsafe impl Allocator for Malloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(c_alloc::dangling(layout));
+        }
+        let ptr = if c_alloc::malloc_aligns(layout, Self::MAX_ALIGN) {
+            unsafe { libc::malloc(layout.size()) }
+        } else {
+            Self::aligned_alloc(layout)
+        };
+        c_alloc::to_slice(ptr, layout.size())
+    }
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(c_alloc::dangling(layout));
+        }
+        if !c_alloc::malloc_aligns(layout, Self::MAX_ALIGN) {
+            let ptr = self.allocate(layout)?;
+            unsafe { ptr.cast::<u8>().write_bytes(0, layout.size()) };
+            return Ok(ptr);
+        }
+        let ptr = unsafe { libc::calloc(layout.size(), 1) };
+        c_alloc::to_slice(ptr, layout.size())
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            unsafe { libc::free(ptr.as_ptr().cast()) }
+        }
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.realloc(ptr, old_layout, new_layout) }
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { self.realloc(ptr, old_layout, new_layout) }
+    }
+}
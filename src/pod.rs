@@ -0,0 +1,54 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{alloc_shim::Allocator, Allocation};
+
+/// Typed views for [`bytemuck`] types.
+impl<A: Allocator> Allocation<A> {
+    /// View the whole allocation as a slice of `T`.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the allocation must be initialized. Any initialized bit pattern is a valid `T`, since `T` is [`Pod`].
+    /// Use [`fill_zeroable`](Self::fill_zeroable) to initialize the allocation first.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is not aligned enough for a `T`, or its size is not a multiple of the size of `T`,
+    /// see [`bytemuck::cast_slice`].
+    pub unsafe fn as_pod_slice<T: Pod>(&self) -> &[T] {
+        let bytes =
+            unsafe { core::slice::from_raw_parts(self.as_ptr::<u8>().as_ptr(), self.len()) };
+        bytemuck::cast_slice(bytes)
+    }
+    /// View the whole allocation as a mutable slice of `T`.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the allocation must be initialized, see [`as_pod_slice`](Self::as_pod_slice).
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is not aligned enough for a `T`, or its size is not a multiple of the size of `T`,
+    /// see [`bytemuck::cast_slice_mut`].
+    pub unsafe fn as_pod_slice_mut<T: Pod>(&mut self) -> &mut [T] {
+        let bytes =
+            unsafe { core::slice::from_raw_parts_mut(self.as_ptr::<u8>().as_ptr(), self.len()) };
+        bytemuck::cast_slice_mut(bytes)
+    }
+    /// Zero the allocation and view it as a mutable slice of as many `T` as fit into it.
+    ///
+    /// All bytes of the allocation are zeroed, including trailing bytes that do not fit a whole `T`. Afterwards,
+    /// the allocation is initialized and [`as_pod_slice`](Self::as_pod_slice) can be called without worry.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation is not aligned enough to contain a `T`.
+    pub fn fill_zeroable<T: Zeroable>(&mut self) -> &mut [T] {
+        for byte in self.as_bytes_mut() {
+            byte.write(0);
+        }
+        let len = self.reinterpret::<T>().len();
+        // SAFETY: Zeroed memory is a valid `T`, alignment has been checked by `reinterpret`
+        unsafe { core::slice::from_raw_parts_mut(self.as_ptr::<T>().as_ptr(), len) }
+    }
+}
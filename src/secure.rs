@@ -0,0 +1,70 @@
+use core::{alloc::Layout, mem::MaybeUninit, sync::atomic};
+
+use crate::{
+    alloc_shim::{Allocator, Global},
+    Allocation,
+};
+
+/// An allocation that overwrites its memory with zeroes before it is deallocated.
+///
+/// This is intended for sensitive data, such as key material, that should not linger in freed memory.
+/// The zeroes are written with volatile writes, so that the compiler can not optimize them away.
+///
+/// Note that reallocating an [`Allocation`] might leave copies of the data behind, hence the memory can only
+/// be accessed, but not resized through this wrapper.
+pub struct SecureAllocation<A: Allocator = Global> {
+    inner: Allocation<A>,
+}
+
+impl SecureAllocation {
+    /// Allocate new memory for the given layout.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new(layout: Layout) -> Self {
+        Self::new_in(layout, Global)
+    }
+}
+
+impl<A: Allocator> SecureAllocation<A> {
+    /// Allocate new memory for the given layout in a given allocator.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_in(layout: Layout, alloc: A) -> Self {
+        Self::from(Allocation::new_in(layout, alloc))
+    }
+    /// Get the layout of the underlying allocation, see [`Allocation::layout`].
+    pub fn layout(&self) -> Layout {
+        self.inner.layout()
+    }
+    /// View the allocation as a slice of possibly uninitialized bytes.
+    pub fn as_bytes(&self) -> &[MaybeUninit<u8>] {
+        self.inner.as_bytes()
+    }
+    /// View the allocation as a mutable slice of possibly uninitialized bytes.
+    pub fn as_bytes_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        self.inner.as_bytes_mut()
+    }
+}
+
+impl<A: Allocator> From<Allocation<A>> for SecureAllocation<A> {
+    fn from(inner: Allocation<A>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<A: Allocator> Drop for SecureAllocation<A> {
+    fn drop(&mut self) {
+        let ptr = self.inner.as_ptr::<u8>().as_ptr();
+        for i in 0..self.inner.layout().size() {
+            unsafe { ptr.add(i).write_volatile(0) };
+        }
+        // Prevent the writes from being reordered after the deallocation by the inner allocation
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
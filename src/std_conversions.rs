@@ -1,8 +1,15 @@
-use core::{alloc::Layout, mem::MaybeUninit, ptr::NonNull};
+use core::{alloc::Layout, fmt, mem::MaybeUninit, pin::Pin, ptr::NonNull};
 
-use alloc::{boxed::Box, vec::Vec};
+#[cfg(not(no_global_oom_handling))]
+use alloc::ffi::FromVecWithNulError;
+use alloc::{boxed::Box, ffi::CString, string::String, vec::Vec};
 
-use crate::{alloc_shim::Allocator, Allocation};
+#[cfg(not(no_global_oom_handling))]
+use crate::alloc_shim::Global;
+use crate::{
+    alloc_shim::{AllocError, Allocator},
+    Allocation,
+};
 
 /// Error when converting an [Allocation] to a [Box].
 #[derive(Debug, Clone)]
@@ -26,6 +33,26 @@ impl BoxConversionError {
     }
 }
 
+impl fmt::Display for BoxConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LayoutMismatch {
+                expected,
+                allocated,
+            } => write!(
+                f,
+                "allocation layout (size {}, align {}) does not match expected (size {}, align {})",
+                allocated.size(),
+                allocated.align(),
+                expected.size(),
+                expected.align(),
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BoxConversionError {}
+
 /// Error when converting an [Allocation] to a [Vec].
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -51,6 +78,20 @@ pub enum VecConversionError {
     ///
     /// If you need this conversion to succeed, open an issue.
     ZeroSizedElements,
+    /// The requested length exceeds the capacity of the allocation.
+    LengthExceedsCapacity {
+        /// requested length in elements
+        len: usize,
+        /// allocated capacity in elements
+        capacity: usize,
+    },
+    /// The byte size of the requested capacity overflows `usize`.
+    CapacityOverflow {
+        /// size of the requested element
+        element_size: usize,
+        /// requested capacity in elements
+        capacity: usize,
+    },
 }
 
 impl VecConversionError {
@@ -69,6 +110,91 @@ impl VecConversionError {
     fn zero_sized_elements() -> Self {
         Self::ZeroSizedElements
     }
+    fn length_exceeds_capacity(len: usize, capacity: usize) -> Self {
+        Self::LengthExceedsCapacity { len, capacity }
+    }
+    fn capacity_overflow(element_size: usize, capacity: usize) -> Self {
+        Self::CapacityOverflow {
+            element_size,
+            capacity,
+        }
+    }
+}
+
+impl fmt::Display for VecConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlignMismatch {
+                expected,
+                allocated,
+            } => write!(
+                f,
+                "allocation alignment {allocated} does not match expected element alignment {expected}"
+            ),
+            Self::SlackCapacity {
+                element_size,
+                allocated,
+            } => write!(
+                f,
+                "allocated size {allocated} is not a multiple of the element size {element_size}"
+            ),
+            Self::ZeroSizedElements => f.write_str("can not convert to a vec of zero-sized elements"),
+            Self::LengthExceedsCapacity { len, capacity } => {
+                write!(f, "length {len} exceeds the allocated capacity {capacity}")
+            }
+            Self::CapacityOverflow {
+                element_size,
+                capacity,
+            } => write!(
+                f,
+                "capacity {capacity} of elements with size {element_size} overflows usize"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for VecConversionError {}
+
+/// Error when converting an [Allocation] to either a [Box] or a [Vec].
+///
+/// Useful for functions that might perform either conversion, since both error types convert into this.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// Converting to a [Box] failed.
+    Box(BoxConversionError),
+    /// Converting to a [Vec] failed.
+    Vec(VecConversionError),
+}
+
+impl From<BoxConversionError> for ConversionError {
+    fn from(err: BoxConversionError) -> Self {
+        Self::Box(err)
+    }
+}
+
+impl From<VecConversionError> for ConversionError {
+    fn from(err: VecConversionError) -> Self {
+        Self::Vec(err)
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Box(_) => f.write_str("failed to convert the allocation into a box"),
+            Self::Vec(_) => f.write_str("failed to convert the allocation into a vec"),
+        }
+    }
+}
+
+impl core::error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Box(err) => Some(err),
+            Self::Vec(err) => Some(err),
+        }
+    }
 }
 
 // we can NOT write
@@ -83,7 +209,62 @@ impl<T> TryFrom<crate::Allocation> for Box<MaybeUninit<T>> {
     }
 }
 
-fn check_box_layout<A: Allocator, T>(allocation: &Allocation<A>) -> Result<(), BoxConversionError> {
+impl crate::Allocation {
+    /// Allocate a pinned, uninitialized box for a `T`.
+    ///
+    /// This is a [`Box::pin`] for uninitialized memory. See [`Self::pinned_uninit_in`] for more details.
+    ///
+    /// ```
+    /// # use std::pin::Pin;
+    /// # use untyped_box::Allocation;
+    /// let mut pinned = Allocation::pinned_uninit::<u64>();
+    /// unsafe { pinned.as_mut().get_unchecked_mut().write(7) };
+    /// let pinned = unsafe { Pin::new_unchecked(Pin::into_inner_unchecked(pinned).assume_init()) };
+    /// assert_eq!(*pinned, 7);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    pub fn pinned_uninit<T>() -> Pin<Box<MaybeUninit<T>>> {
+        Self::pinned_uninit_in(Global)
+    }
+    /// Convert the allocation into a [`CString`].
+    ///
+    /// The size of the allocation is the length of the string including the terminating NUL byte, which is
+    /// the layout `Allocation as From<CString>` produces. This fails if the last byte is not the only NUL byte.
+    /// The allocation is reallocated to an alignment of `1` if necessary.
+    ///
+    /// # Safety
+    ///
+    /// All `self.layout().size()` bytes of the allocation must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn try_into_cstring(self) -> Result<CString, FromVecWithNulError> {
+        let bytes = unsafe { self.into_boxed_bytes() };
+        // The vec has no spare capacity, so the string takes over the buffer without reallocating
+        CString::from_vec_with_nul(bytes.into_vec())
+    }
+
+    /// Convert the allocation into an empty [`String`].
+    ///
+    /// This fails under the same conditions as converting into a `Vec<u8>`, see [`Self::try_into_vec`].
+    ///
+    /// The length of the returned string is always `0`. To use the contents of the allocation, set the length
+    /// with [`Vec::set_len`] through [`String::as_mut_vec`], ensuring that the bytes up to the length are valid UTF-8.
+    ///
+    /// See also the opposite conversion `Allocation as From<String>`.
+    pub fn try_into_string(self) -> Result<String, VecConversionError> {
+        let bytes = self.try_into_vec::<u8>()?;
+        debug_assert!(bytes.is_empty());
+        // SAFETY: the empty string is valid UTF-8
+        Ok(unsafe { String::from_utf8_unchecked(bytes) })
+    }
+}
+
+pub(crate) fn check_box_layout<A: Allocator, T>(
+    allocation: &Allocation<A>,
+) -> Result<(), BoxConversionError> {
     let expected = Layout::new::<T>();
     let actual = allocation.layout();
     if expected != actual {
@@ -94,7 +275,7 @@ fn check_box_layout<A: Allocator, T>(allocation: &Allocation<A>) -> Result<(), B
 // TODO: conversion for unsized box/pointer metadata
 // TODO: conversion to ThinBox?
 
-fn check_vec_layout<A: Allocator, T>(
+pub(crate) fn check_vec_layout<A: Allocator, T>(
     allocation: &Allocation<A>,
 ) -> Result<usize, VecConversionError> {
     let expected = Layout::new::<T>();
@@ -110,7 +291,9 @@ fn check_vec_layout<A: Allocator, T>(
     let element_size = expected.size();
     let byte_capacity = actual.size();
     #[allow(clippy::manual_is_multiple_of)] // would require MSRV of 1.87
-    if (element_size == 0 && byte_capacity != 0) || byte_capacity % element_size != 0 {
+    if (element_size == 0 && byte_capacity != 0)
+        || (element_size != 0 && byte_capacity % element_size != 0)
+    {
         return Err(VecConversionError::slack_capacity(
             element_size,
             byte_capacity,
@@ -123,11 +306,62 @@ fn check_vec_layout<A: Allocator, T>(
         return Err(VecConversionError::zero_sized_elements());
     }
 
+    // A `Vec` deallocates (and reallocates) with `Layout::array::<T>(capacity)`. The checks above ensure this is
+    // exactly the layout of the allocation, including alignment.
     let element_capacity = byte_capacity / element_size;
-    debug_assert!(byte_capacity == element_size * element_capacity);
     Ok(element_capacity)
 }
 
+pub(crate) fn check_vec_capacity<A: Allocator, T>(
+    allocation: &Allocation<A>,
+    capacity: usize,
+) -> Result<(), VecConversionError> {
+    let element_size = size_of::<T>();
+    let byte_capacity =
+        element_size
+            .checked_mul(capacity)
+            .ok_or(VecConversionError::capacity_overflow(
+                element_size,
+                capacity,
+            ))?;
+    let _ = check_vec_layout::<_, T>(allocation)?;
+    if byte_capacity != allocation.layout().size() {
+        return Err(VecConversionError::slack_capacity(
+            element_size,
+            allocation.layout().size(),
+        ));
+    }
+    Ok(())
+}
+
+/// Collects the bytes into a new allocation.
+///
+/// The bytes of the iterator are stored at the start of the allocation, but the number of bytes is not tracked.
+/// As with `Allocation as From<Vec<u8>>`, the allocation might be larger than the number of bytes collected.
+#[cfg(not(no_global_oom_handling))]
+impl FromIterator<u8> for crate::Allocation {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<u8>>())
+    }
+}
+
+/// Adopts the buffer of the string, including the terminating NUL byte.
+///
+/// The layout of the allocation has the alignment `1` and the size of
+/// [`as_bytes_with_nul`](CString::as_bytes_with_nul), i.e. the length of the string plus one.
+impl From<CString> for crate::Allocation {
+    fn from(value: CString) -> Self {
+        Self::from(value.into_bytes_with_nul().into_boxed_slice())
+    }
+}
+
+/// The contents of the string are forgotten, as if by a call to [`string.clear()`](String::clear).
+impl From<String> for crate::Allocation {
+    fn from(value: String) -> Self {
+        Self::from(value.into_bytes())
+    }
+}
+
 impl<T> TryFrom<crate::Allocation> for Vec<T> {
     type Error = VecConversionError;
 
@@ -287,6 +521,19 @@ api_impl::allocation_impl! {
     /// This fails if the allocated layout does not match the requested type. The value might not be initialized,
     /// use [`Box::assume_init`] in case you have initialized the memory of this allocation correctly.
     ///
+    /// To obtain a box whose contents are not dropped automatically, convert to a
+    /// [`ManuallyDrop<T>`](core::mem::ManuallyDrop), which has the same layout as `T`:
+    ///
+    /// ```
+    /// # use std::mem::ManuallyDrop;
+    /// # use untyped_box::Allocation;
+    /// let alloc = Allocation::from_value(ManuallyDrop::new(String::from("leaked")));
+    /// let boxed = alloc.try_into_box::<ManuallyDrop<String>>().unwrap();
+    /// let mut boxed = unsafe { boxed.assume_init() };
+    /// // Dropping `boxed` only frees the box, not the string.
+    /// # unsafe { ManuallyDrop::drop(&mut boxed) };
+    /// ```
+    ///
     /// See also the opposite conversion `Allocation as From<Box<_>>`.
     // TODO: add intro-doc link to `<Allocation as From<Box<_>>>`
     pub fn try_into_box<T>(self) -> Result<ABox<MaybeUninit<T>, A>, BoxConversionError> {
@@ -298,6 +545,186 @@ api_impl::allocation_impl! {
         Ok(unsafe { api_impl::box_from_parts!(ptr, alloc) })
     }
 
+    /// Convert the allocation into a box, leaving a zero-sized allocation behind.
+    ///
+    /// This performs the same check as [`Self::try_into_box`]. On success, `self` is replaced by a zero-sized
+    /// allocation in a clone of its allocator, which is useful when the allocation can not be moved out, e.g. in a
+    /// [`Drop`] implementation or a pool. On failure, `self` is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the zero-sized allocation could not be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn take_into_box<T>(&mut self) -> Result<ABox<MaybeUninit<T>, A>, BoxConversionError>
+    where
+        A: Clone,
+    {
+        let () = check_box_layout::<_, T>(self)?;
+        self.take().try_into_box()
+    }
+
+    /// Convert the allocation into a box without checking the layout.
+    ///
+    /// This is the unchecked version of [`Self::try_into_box`]. In debug builds, the layout is still checked.
+    ///
+    /// # Safety
+    ///
+    /// The layout of the allocation must be exactly `Layout::new::<T>()`.
+    pub unsafe fn assume_fits_into_box<T>(self) -> ABox<MaybeUninit<T>, A> {
+        debug_assert!(
+            check_box_layout::<_, T>(&self).is_ok(),
+            "allocation layout does not match the layout of the box"
+        );
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        unsafe { api_impl::box_from_parts!(ptr, alloc) }
+    }
+
+    /// Convert the allocation into a pinned box of an initialized value.
+    ///
+    /// This is [`Self::into_box_assume_init`] followed by [`Box::into_pin`].
+    ///
+    /// # Safety
+    ///
+    /// The memory of the allocation must contain an initialized, valid value of type `T`.
+    pub unsafe fn into_pinned_box<T>(self) -> Result<Pin<ABox<T, A>>, BoxConversionError>
+    where
+        A: 'static,
+    {
+        let boxed = unsafe { self.into_box_assume_init::<T>()? };
+        Ok(Box::into_pin(boxed))
+    }
+
+    /// Convert the allocation into a box of an array of possibly uninitialized elements.
+    ///
+    /// This fails if the allocated layout does not match `[T; N]`. In contrast to `try_into_box::<[T; N]>()`,
+    /// the elements can be initialized one at a time.
+    ///
+    /// The opposite conversion is covered by `Allocation as From<Box<_>>`, since an array is a sized type.
+    pub fn try_into_array_box<T, const N: usize>(
+        self,
+    ) -> Result<ABox<[MaybeUninit<T>; N], A>, BoxConversionError> {
+        let () = check_box_layout::<_, [T; N]>(&self)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        Ok(unsafe { api_impl::box_from_parts!(ptr, alloc) })
+    }
+
+    /// Convert the allocation into a box of a possibly uninitialized array.
+    ///
+    /// This is the same as `try_into_box::<[T; N]>()`. In contrast to [`Self::try_into_array_box`], the whole array
+    /// is a single [`MaybeUninit`] that can be initialized at once, e.g. by writing an array value.
+    pub fn try_into_uninit_array_box<T, const N: usize>(
+        self,
+    ) -> Result<ABox<MaybeUninit<[T; N]>, A>, BoxConversionError> {
+        self.try_into_box::<[T; N]>()
+    }
+
+    /// Convert the allocation into a box of an initialized value.
+    ///
+    /// This performs the same layout check as [`Self::try_into_box`], but skips the intermediate
+    /// `Box<MaybeUninit<T>>`.
+    ///
+    /// ```
+    /// # use untyped_box::Allocation;
+    /// let alloc = Allocation::from_value(7u32);
+    /// let boxed = unsafe { alloc.into_box_assume_init::<u32>() }.unwrap();
+    /// assert_eq!(*boxed, 7);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The memory of the allocation must contain an initialized, valid value of type `T`.
+    pub unsafe fn into_box_assume_init<T>(self) -> Result<ABox<T, A>, BoxConversionError> {
+        let () = check_box_layout::<_, T>(&self)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        Ok(unsafe { api_impl::box_from_parts!(ptr, alloc) })
+    }
+
+    /// Allocate a pinned, uninitialized box for a `T` in the given allocator.
+    ///
+    /// The memory is allocated for exactly `Layout::new::<T>()`. Initialize the value in place, e.g. through
+    /// [`Pin::map_unchecked_mut`], and then convert with [`Box::assume_init`] without moving it.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn pinned_uninit_in<T>(alloc: A) -> Pin<ABox<MaybeUninit<T>, A>>
+    where
+        A: 'static,
+    {
+        // The requested layout fits the memory block, see `Allocation::into_parts_with_alloc`.
+        let (ptr, _, alloc) = Self::new_in(Layout::new::<T>(), alloc).into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        let boxed = unsafe { api_impl::box_from_parts!(ptr, alloc) };
+        Box::into_pin(boxed)
+    }
+
+    /// Convert the allocation into a boxed slice of possibly uninitialized bytes.
+    ///
+    /// The slice covers the whole allocation, i.e. its length is `self.layout().size()`. Since a boxed slice of bytes
+    /// is deallocated with an alignment of `1`, the allocation is reallocated if it is more strictly aligned.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_boxed_uninit_bytes(mut self) -> ABox<[MaybeUninit<u8>], A> {
+        let len = self.layout().size();
+        let layout = Layout::array::<u8>(len).expect("size of an allocation fits into a layout");
+        self.try_reallocate(layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout));
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr().cast(), len);
+        unsafe { api_impl::box_from_parts!(ptr, alloc) }
+    }
+
+    /// Convert the allocation into a boxed slice of bytes.
+    ///
+    /// The slice covers the whole allocation, see [`Self::into_boxed_uninit_bytes`].
+    ///
+    /// # Safety
+    ///
+    /// All `self.layout().size()` bytes of the allocation must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn into_boxed_bytes(self) -> ABox<[u8], A> {
+        let boxed = self.into_boxed_uninit_bytes();
+        let (ptr, alloc) = api_impl::box_to_parts!(boxed);
+        unsafe { api_impl::box_from_parts!(ptr as *mut [u8], alloc) }
+    }
+
+    /// Shrink the allocation to `used` bytes and convert it into a boxed byte slice of that length.
+    ///
+    /// This is the natural finisher for a buffer that has been filled front to back, producing a right-sized `Box<[u8]>`.
+    /// The allocation is reallocated to exactly `Layout::array::<u8>(used)`, lowering its alignment to `1`.
+    ///
+    /// Returns an error when the memory could not be reallocated. The allocation is dropped in this case.
+    ///
+    /// # Safety
+    ///
+    /// The first `used` bytes of the allocation must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// If `used` is larger than the size of the allocation.
+    pub unsafe fn into_used_byte_box(mut self, used: usize) -> Result<ABox<[u8], A>, AllocError> {
+        assert!(
+            used <= self.layout().size(),
+            "used bytes exceed the allocation size"
+        );
+        let layout = Layout::array::<u8>(used).map_err(|_| AllocError)?;
+        self.try_reallocate(layout)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), used);
+        Ok(unsafe { api_impl::box_from_parts!(ptr, alloc) })
+    }
+
     /// Convert the allocation into a [`Vec`].
     ///
     /// This fails if the allocated size is not a multiple of the requested element size, or if the element type is zero-sized.
@@ -313,6 +740,146 @@ api_impl::allocation_impl! {
         let ptr = ptr.as_ptr().cast();
         Ok(unsafe { api_impl::vec_from_parts!(ptr, capacity, alloc) })
     }
+
+    /// Convert the allocation into a [`Vec`], leaving a zero-sized allocation behind.
+    ///
+    /// This performs the same checks as [`Self::try_into_vec`]. On success, `self` is replaced by a zero-sized
+    /// allocation, see [`Self::take_into_box`]. On failure, `self` is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the zero-sized allocation could not be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn take_into_vec<T>(&mut self) -> Result<AVec<T, A>, VecConversionError>
+    where
+        A: Clone,
+    {
+        let _ = check_vec_layout::<_, T>(self)?;
+        self.take().try_into_vec()
+    }
+
+    /// Convert the allocation into a [`Vec`] of the given length.
+    ///
+    /// This fails under the same conditions as [`try_into_vec`](Self::try_into_vec), or if `len` exceeds the
+    /// capacity of the vec.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements in the allocation must be initialized, see [`Vec::set_len`].
+    pub unsafe fn into_vec_with_len<T>(self, len: usize) -> Result<AVec<T, A>, VecConversionError> {
+        let capacity = check_vec_layout::<_, T>(&self)?;
+        if len > capacity {
+            return Err(VecConversionError::length_exceeds_capacity(len, capacity));
+        }
+        let mut vec = self.try_into_vec::<T>()?;
+        unsafe { vec.set_len(len) };
+        Ok(vec)
+    }
+
+    /// Convert the allocation into a [`Vec`] with the given capacity.
+    ///
+    /// This is the same as [`try_into_vec_with_exact_capacity`](Self::try_into_vec_with_exact_capacity).
+    pub fn try_into_vec_with_capacity<T>(self, capacity: usize) -> Result<AVec<T, A>, VecConversionError> {
+        self.try_into_vec_with_exact_capacity(capacity)
+    }
+
+    /// Convert the allocation into a [`Vec`] with exactly the given capacity.
+    ///
+    /// In addition to the checks of [`try_into_vec`](Self::try_into_vec), this fails if `capacity * size_of::<T>()`
+    /// is not exactly the size of the allocation, or with [`VecConversionError::CapacityOverflow`] if the byte size
+    /// of `capacity` elements is not representable. A mismatching capacity is reported as
+    /// [`VecConversionError::SlackCapacity`].
+    ///
+    /// This lets callers assert the capacity they expect, e.g. the length of a boxed slice the allocation came from:
+    ///
+    /// ```
+    /// # use untyped_box::Allocation;
+    /// let alloc = Allocation::from(vec![1u16, 2, 3].into_boxed_slice());
+    /// let vec = alloc.try_into_vec_with_exact_capacity::<u16>(3).unwrap();
+    /// assert_eq!(vec.capacity(), 3);
+    /// ```
+    pub fn try_into_vec_with_exact_capacity<T>(
+        self,
+        capacity: usize,
+    ) -> Result<AVec<T, A>, VecConversionError> {
+        let () = check_vec_capacity::<_, T>(&self, capacity)?;
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        Ok(unsafe { api_impl::vec_from_parts!(ptr, capacity, alloc) })
+    }
+
+    /// Convert the allocation into a [`Vec`], discarding trailing bytes that do not fit a whole element.
+    ///
+    /// In contrast to [`try_into_vec`](Self::try_into_vec), an allocation whose size is not a multiple of the
+    /// element size is first shrunk to the largest multiple. Returns the vec together with the number of discarded
+    /// bytes. Unlike [`into_vec_fixup`](Self::into_vec_fixup), the alignment is not adjusted.
+    ///
+    /// ```
+    /// # use std::alloc::Layout;
+    /// # use untyped_box::Allocation;
+    /// let alloc = Allocation::new(Layout::from_size_align(17, 4).unwrap());
+    /// let (vec, discarded) = alloc.try_into_vec_lossy::<i32>().unwrap();
+    /// assert_eq!((vec.capacity(), discarded), (4, 1));
+    /// ```
+    ///
+    /// This fails if the alignment of the allocation does not match the alignment of `T`, or if the element type
+    /// is zero-sized.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn try_into_vec_lossy<T>(mut self) -> Result<(AVec<T, A>, usize), VecConversionError> {
+        let element_align = align_of::<T>();
+        let alloc_align = self.layout().align();
+        if element_align != alloc_align {
+            return Err(VecConversionError::align_mismatch(
+                element_align,
+                alloc_align,
+            ));
+        }
+        if size_of::<T>() == 0 {
+            return Err(VecConversionError::zero_sized_elements());
+        }
+        let size = self.layout().size();
+        let capacity = size / size_of::<T>();
+        let layout = Layout::array::<T>(capacity).expect("capacity fits into the allocation");
+        self.try_reallocate(layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout));
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        Ok((
+            unsafe { api_impl::vec_from_parts!(ptr, capacity, alloc) },
+            size - layout.size(),
+        ))
+    }
+
+    /// Convert the allocation into a [`Vec`], reallocating it to fit the vec's layout if necessary.
+    ///
+    /// In contrast to [`try_into_vec`](Self::try_into_vec), slack bytes that do not fit a whole element are dropped
+    /// and the alignment is adjusted to the alignment of `T`. The allocation is reallocated to exactly
+    /// `Layout::array::<T>(capacity)` where the capacity is the number of whole elements fitting into the allocation.
+    /// This guarantees that the layout the vec uses to deallocate its buffer is correct.
+    ///
+    /// This fails only if the element type is zero-sized, see [`VecConversionError::ZeroSizedElements`].
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when the memory could not be reallocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_vec_fixup<T>(mut self) -> Result<AVec<T, A>, VecConversionError> {
+        if size_of::<T>() == 0 {
+            return Err(VecConversionError::zero_sized_elements());
+        }
+        let capacity = self.layout().size() / size_of::<T>();
+        let layout = Layout::array::<T>(capacity).expect("capacity fits into the allocation");
+        self.try_reallocate(layout)
+            .unwrap_or_else(|AllocError| crate::alloc_shim::handle_alloc_error(layout));
+        // The requested layout fits the memory block, see `Allocation::into_parts_with_alloc`.
+        let (ptr, _, alloc) = self.into_parts_with_alloc();
+        let ptr = ptr.as_ptr().cast();
+        Ok(unsafe { api_impl::vec_from_parts!(ptr, capacity, alloc) })
+    }
 }
 
 // This has to appear side-by-side with allocation_impl because it relies on `A` and `ABox` to be defined
@@ -321,6 +888,9 @@ api_impl::from_box_impl! {
     /// The value in the box will not be dropped, as if passed to [`forget`](core::mem::forget).
     /// Use the inverse (fallible) conversion to recover the value.
     ///
+    /// The layout of the allocation is [`Layout::for_value`] of the boxed value. In particular, a boxed slice `[T]`
+    /// of length `n` results in `Layout::array::<T>(n)`, so that converting into a `Vec<T>` recovers a capacity of `n`.
+    ///
     /// ```
     /// # use std::mem::MaybeUninit;
     /// # use untyped_box::Allocation;
@@ -7,10 +7,95 @@
 //! ```
 
 use alloc::boxed::Box;
-use core::alloc::Layout;
+use core::{alloc::Layout, cell::Cell, ptr::NonNull};
 
+use crate::alloc_shim::{AllocError, Allocator, Global};
 use crate::*;
 
+/// Counts the calls made to the allocator, forwarding to [`Global`].
+#[derive(Default)]
+struct Counting {
+    calls: Cell<usize>,
+}
+
+impl Counting {
+    fn count(&self) {
+        self.calls.set(self.calls.get() + 1);
+    }
+}
+
+unsafe impl Allocator for &Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.count();
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.count();
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.count();
+        unsafe { Global.grow(ptr, old_layout, new_layout) }
+    }
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.count();
+        unsafe { Global.grow_zeroed(ptr, old_layout, new_layout) }
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.count();
+        unsafe { Global.shrink(ptr, old_layout, new_layout) }
+    }
+}
+
+/// Rounds every allocation up to a multiple of 64 bytes, forwarding to [`Global`].
+struct OverAllocating;
+
+impl OverAllocating {
+    fn padded(layout: Layout) -> Layout {
+        Layout::from_size_align(layout.size().next_multiple_of(64), layout.align()).unwrap()
+    }
+}
+
+unsafe impl Allocator for OverAllocating {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Global.allocate(Self::padded(layout))
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, Self::padded(layout)) }
+    }
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { Global.grow(ptr, Self::padded(old_layout), Self::padded(new_layout)) }
+    }
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { Global.shrink(ptr, Self::padded(old_layout), Self::padded(new_layout)) }
+    }
+}
+
 #[test]
 fn test_alloc() {
     let _ = Allocation::new(Layout::from_size_align(0, 1).unwrap());
@@ -88,3 +173,1780 @@ fn zeroed() {
         &[42, 0]
     );
 }
+
+#[test]
+fn write_value() {
+    let mut alloc = Allocation::new(Layout::new::<u32>());
+    assert_eq!(*alloc.write_value(42u32), 42);
+    let boxed = alloc.try_into_box::<u32>().unwrap();
+    let boxed = unsafe { boxed.assume_init() };
+    assert_eq!(*boxed, 42);
+}
+
+#[test]
+fn convert_vec_exact_layout() {
+    // over-aligned allocations would be deallocated with the wrong alignment by the vec
+    let over_aligned = Allocation::new(Layout::from_size_align(32, 8).unwrap());
+    assert!(matches!(
+        over_aligned.try_into_vec::<u32>(),
+        Err(VecConversionError::AlignMismatch { .. })
+    ));
+    // padded allocations would be deallocated with the wrong size by the vec
+    let padded = Allocation::new(Layout::from_size_align(30, 4).unwrap());
+    assert!(matches!(
+        padded.try_into_vec::<u32>(),
+        Err(VecConversionError::SlackCapacity { .. })
+    ));
+
+    let alloc = Allocation::new(Layout::array::<u32>(4).unwrap());
+    let mut vec = alloc.try_into_vec::<u32>().unwrap();
+    assert_eq!(vec.capacity(), 4);
+    // Under miri, growing and dropping the vec checks the layout passed to the allocator
+    vec.extend(0..5);
+    drop(vec);
+}
+
+#[cfg(feature = "nightly-std-conversions")]
+mod allocator_api {
+    use super::*;
+
+    /// Asserts that memory is deallocated with exactly the layout it was allocated with.
+    #[derive(Default)]
+    struct ExactLayout {
+        live: Cell<Option<Layout>>,
+    }
+
+    unsafe impl Allocator for &ExactLayout {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            assert!(self.live.replace(Some(layout)).is_none());
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            assert_eq!(self.live.take(), Some(layout));
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn drop_converted_vec() {
+        let exact = ExactLayout::default();
+        let alloc = Allocation::new_in(Layout::array::<u64>(3).unwrap(), &exact);
+        let mut vec = alloc.try_into_vec::<u64>().unwrap();
+        vec.push(1);
+        drop(vec);
+        assert!(exact.live.get().is_none());
+    }
+
+    #[test]
+    fn typed_allocation_over_allocating() {
+        let mut typed = TypedAllocation::<u32, _>::new_in(OverAllocating);
+        typed.write(7);
+        let boxed = typed.assume_init();
+        assert_eq!(*boxed, 7);
+    }
+}
+
+#[test]
+fn from_value() {
+    let alloc = Allocation::from_value(-7i32);
+    let boxed = unsafe { alloc.try_into_box::<i32>().unwrap().assume_init() };
+    assert_eq!(*boxed, -7);
+
+    #[derive(Debug, PartialEq)]
+    struct Large {
+        data: [u64; 16],
+        tag: u8,
+    }
+    let value = Large {
+        data: core::array::from_fn(|i| i as u64 * 3),
+        tag: 0xab,
+    };
+    let expected = Large { ..value };
+    let alloc = Allocation::from_value(value);
+    assert_eq!(alloc.layout(), Layout::new::<Large>());
+    let boxed = unsafe { alloc.try_into_box::<Large>().unwrap().assume_init() };
+    assert_eq!(*boxed, expected);
+}
+
+#[test]
+fn batch_resize() {
+    let counting = Counting::default();
+    let mut alloc = Allocation::new_in(Layout::new::<u8>(), &counting);
+    counting.calls.set(0);
+
+    let mut batch = alloc.begin_batch();
+    for n in 1..=5 {
+        batch.request(Layout::array::<u32>(n * 8).unwrap()).unwrap();
+    }
+    assert_eq!(batch.pending(), Layout::array::<u32>(40).unwrap());
+    drop(batch);
+    assert_eq!(counting.calls.get(), 1);
+    assert_eq!(alloc.layout(), Layout::array::<u32>(40).unwrap());
+
+    // Smaller requests do not shrink, so committing is a no-op
+    let mut batch = alloc.begin_batch();
+    batch.request(Layout::new::<u32>()).unwrap();
+    batch.commit().unwrap();
+    assert_eq!(counting.calls.get(), 1);
+}
+
+#[test]
+fn read_value() {
+    let mut alloc = Allocation::new(Layout::new::<[u16; 4]>());
+    alloc.write_value([1u16, 2, 3, 4]);
+    assert_eq!(unsafe { alloc.read_value::<[u16; 4]>() }, [1, 2, 3, 4]);
+    // reading leaves the value in place
+    assert_eq!(unsafe { alloc.read_value::<u16>() }, 1);
+}
+
+#[test]
+fn convert_box_manually_drop() {
+    use core::mem::ManuallyDrop;
+
+    struct Flag<'a>(&'a Cell<bool>);
+    impl Drop for Flag<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    assert_eq!(Layout::new::<ManuallyDrop<Flag>>(), Layout::new::<Flag>());
+    let dropped = Cell::new(false);
+    let mut alloc = Allocation::new(Layout::new::<Flag>());
+    alloc.write_value(Flag(&dropped));
+    let boxed = alloc.try_into_box::<ManuallyDrop<Flag>>().unwrap();
+    let boxed = unsafe { boxed.assume_init() };
+    drop(boxed);
+    assert!(!dropped.get());
+}
+
+#[test]
+fn accessors() {
+    let layout = Layout::from_size_align(24, 8).unwrap();
+    let alloc = Allocation::new(layout);
+    assert_eq!(alloc.len(), 24);
+    assert_eq!(alloc.size(), 24);
+    assert_eq!(alloc.align(), 8);
+    assert!(!alloc.is_empty());
+
+    let empty = Allocation::new(Layout::new::<()>());
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+}
+
+#[cfg(feature = "generation")]
+#[test]
+fn generation() {
+    let mut alloc = Allocation::new(Layout::new::<u32>());
+    assert_eq!(alloc.generation(), 0);
+    alloc.realloc(Layout::new::<u32>());
+    assert_eq!(alloc.generation(), 0);
+    alloc.realloc(Layout::new::<[u32; 16]>());
+    assert_eq!(alloc.generation(), 1);
+    alloc.realloc_zeroed(Layout::new::<[u32; 32]>());
+    assert_eq!(alloc.generation(), 2);
+    alloc.realloc(Layout::new::<[u32; 32]>());
+    assert_eq!(alloc.generation(), 2);
+    // fits into the current block
+    alloc.realloc(Layout::new::<u32>());
+    assert_eq!(alloc.generation(), 2);
+    alloc.realloc_zeroed(Layout::new::<u32>());
+    assert_eq!(alloc.generation(), 2);
+    alloc.realloc_zeroed(Layout::new::<[u32; 64]>());
+    assert_eq!(alloc.generation(), 3);
+}
+
+#[test]
+fn capacity_for() {
+    let empty_alloc = Allocation::new(Layout::new::<[i32; 0]>());
+    assert_eq!(empty_alloc.byte_capacity(), 0);
+    assert_eq!(empty_alloc.capacity_for::<i32>(), 0);
+
+    let filled_alloc = Allocation::new(Layout::new::<[i32; 32]>());
+    assert_eq!(filled_alloc.byte_capacity(), 128);
+    assert_eq!(filled_alloc.capacity_for::<i32>(), 32);
+    assert_eq!(filled_alloc.capacity_for::<u8>(), 128);
+    assert_eq!(filled_alloc.capacity_for::<u64>(), 0);
+    assert_eq!(filled_alloc.capacity_for::<()>(), 0);
+    assert_eq!(filled_alloc.try_into_vec::<i32>().unwrap().capacity(), 32);
+}
+
+#[test]
+fn into_used_byte_box() {
+    let alloc = Allocation::new(Layout::from_size_align(64, 8).unwrap());
+    let bytes = alloc.as_slice().as_ptr() as *mut u8;
+    for i in 0..10 {
+        unsafe { bytes.add(i).write(i as u8) };
+    }
+    let boxed = unsafe { alloc.into_used_byte_box(10) }.unwrap();
+    assert_eq!(&*boxed, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let alloc = Allocation::new(Layout::from_size_align(16, 4).unwrap());
+    let boxed = unsafe { alloc.into_used_byte_box(0) }.unwrap();
+    assert!(boxed.is_empty());
+}
+
+#[test]
+fn fits() {
+    #[repr(align(32))]
+    struct OverAligned(#[allow(dead_code)] u8);
+
+    let alloc = Allocation::new(Layout::from_size_align(16, 16).unwrap());
+    assert!(alloc.is_aligned_for::<u64>());
+    assert!(alloc.is_aligned_for::<u128>());
+    assert!(!alloc.is_aligned_for::<OverAligned>());
+    assert!(alloc.fits::<u128>());
+    assert!(alloc.fits::<[u64; 2]>());
+    assert!(!alloc.fits::<[u64; 3]>());
+    assert!(!alloc.fits::<OverAligned>());
+}
+
+#[test]
+fn pinned_uninit() {
+    use core::{marker::PhantomPinned, pin::Pin};
+
+    struct SelfRef {
+        value: u32,
+        this: *const SelfRef,
+        _pin: PhantomPinned,
+    }
+
+    let mut pinned = Allocation::pinned_uninit::<SelfRef>();
+    let addr = &*pinned as *const _ as *const SelfRef;
+    unsafe {
+        let slot = pinned.as_mut().get_unchecked_mut();
+        slot.write(SelfRef {
+            value: 5,
+            this: slot.as_ptr(),
+            _pin: PhantomPinned,
+        });
+    }
+    let pinned: Pin<Box<SelfRef>> =
+        unsafe { Pin::new_unchecked(Pin::into_inner_unchecked(pinned).assume_init()) };
+    assert_eq!(pinned.value, 5);
+    assert_eq!(pinned.this, addr);
+    assert_eq!(&*pinned as *const SelfRef, addr);
+}
+
+#[test]
+fn into_vec_fixup() {
+    let over_aligned = Allocation::new(Layout::from_size_align(34, 64).unwrap());
+    let mut vec = over_aligned.into_vec_fixup::<u32>().unwrap();
+    assert_eq!(vec.capacity(), 8);
+    // Under miri, growing and dropping the vec checks the layout passed to the allocator
+    vec.extend(0..9);
+    drop(vec);
+
+    let exact = Allocation::new(Layout::array::<u16>(3).unwrap());
+    let vec = exact.into_vec_fixup::<u16>().unwrap();
+    assert_eq!(vec.capacity(), 3);
+
+    let alloc = Allocation::new(Layout::new::<u32>());
+    assert!(matches!(
+        alloc.into_vec_fixup::<()>(),
+        Err(VecConversionError::ZeroSizedElements)
+    ));
+}
+
+#[test]
+fn as_ptr_range() {
+    let alloc = Allocation::new(Layout::new::<[u32; 5]>());
+    let range = alloc.as_ptr_range();
+    assert_eq!(range.start, alloc.as_ptr());
+    assert_eq!(
+        range.end.as_ptr().addr() - range.start.as_ptr().addr(),
+        alloc.layout().size()
+    );
+
+    let empty = Allocation::new(Layout::new::<()>());
+    let range = empty.as_ptr_range();
+    assert_eq!(range.start, range.end);
+}
+
+#[test]
+fn contains_ptr() {
+    let alloc = Allocation::new(Layout::new::<[u32; 4]>());
+    let start = alloc.as_ptr::<u32>().as_ptr();
+    assert!(alloc.contains_ptr(start));
+    assert!(alloc.contains_ptr(start.wrapping_add(2)));
+    assert!(alloc.contains_ptr(start.cast::<u8>().wrapping_add(15)));
+    assert!(!alloc.contains_ptr(start.wrapping_add(4)));
+    assert!(!alloc.contains_ptr(start.wrapping_sub(1)));
+    assert!(!alloc.contains_ptr(&0u32));
+
+    let empty = Allocation::new(Layout::new::<()>());
+    assert!(!empty.contains_ptr(empty.as_ptr::<u8>().as_ptr()));
+}
+
+#[test]
+fn fits_layout() {
+    let layout = Layout::from_size_align(32, 8).unwrap();
+    let alloc = Allocation::new(layout);
+    assert!(alloc.fits_layout(layout));
+    assert!(alloc.fits_layout(Layout::from_size_align(16, 4).unwrap()));
+    assert!(!alloc.fits_layout(Layout::from_size_align(33, 8).unwrap()));
+    assert!(!alloc.fits_layout(Layout::from_size_align(8, 16).unwrap()));
+}
+
+#[test]
+fn realloc_within_block() {
+    let layout = Layout::new::<[u32; 3]>();
+    let mut alloc = Allocation::new_in(layout, OverAllocating);
+    assert_eq!(alloc.layout().size(), 64);
+    let ptr = alloc.as_ptr::<u8>();
+
+    alloc.realloc(layout);
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(alloc.layout().size(), 64);
+    assert_eq!(alloc.requested_layout(), layout);
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), 0);
+
+    alloc.realloc(Layout::new::<[u32; 16]>());
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    alloc.realloc(Layout::new::<u8>());
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(alloc.requested_layout(), Layout::new::<u8>());
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), 0);
+}
+
+#[test]
+fn realloc_shrink_then_grow() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 8]>());
+    alloc.write_value([0xffu8; 8]);
+    let ptr = alloc.as_ptr::<u8>();
+
+    // realloc keeps the block and exposes the old bytes again
+    alloc.realloc(Layout::new::<[u8; 2]>());
+    alloc.realloc(Layout::new::<[u8; 8]>());
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, [0xff; 8]);
+
+    // realloc_zeroed keeps the block as well, but clears the bytes past the requested size
+    alloc.realloc(Layout::new::<[u8; 2]>());
+    alloc.realloc_zeroed(Layout::new::<[u8; 8]>());
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(
+        unsafe { alloc.read_value::<[u8; 8]>() },
+        [0xff, 0xff, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn grow_shrink() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 4]>());
+    alloc.write_value([1u8, 2, 3, 4]);
+    alloc.grow(Layout::new::<[u8; 64]>());
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 64]>());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 4]>() }, [1, 2, 3, 4]);
+    alloc.shrink(Layout::new::<[u8; 2]>());
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 2]>());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 2]>() }, [1, 2]);
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), 2);
+}
+
+#[test]
+fn reserve() {
+    let counting = Counting::default();
+    let mut alloc = Allocation::new_in(Layout::new::<u8>(), &counting);
+    counting.calls.set(0);
+    for _ in 0..100 {
+        alloc.reserve(1);
+    }
+    assert_eq!(alloc.requested_layout().size(), 101);
+    assert!(alloc.layout().size() >= 101);
+    // 1 -> 2 -> 4 -> ... -> 128
+    assert_eq!(counting.calls.get(), 7);
+
+    assert!(alloc.try_reserve(usize::MAX).is_err());
+    assert_eq!(alloc.requested_layout().size(), 101);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 100]>(), OverAllocating);
+    assert_eq!(alloc.layout().size(), 128);
+    alloc.realloc(Layout::new::<[u8; 4]>());
+    assert_eq!(alloc.layout().size(), 128);
+    alloc.shrink_to_fit();
+    assert_eq!(alloc.layout().size(), 64);
+    assert_eq!(alloc.requested_layout(), Layout::new::<[u8; 4]>());
+
+    let mut alloc = Allocation::new(Layout::new::<[u32; 8]>());
+    alloc.realloc(Layout::new::<[u32; 2]>());
+    alloc.shrink_to_fit();
+    assert_eq!(alloc.layout(), Layout::new::<[u32; 2]>());
+    // Nothing to reclaim
+    #[cfg(feature = "generation")]
+    let generation = alloc.generation();
+    alloc.shrink_to_fit();
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), generation);
+}
+
+#[test]
+fn resize_bytes() {
+    let mut alloc = Allocation::new(Layout::from_size_align(4, 4).unwrap());
+    alloc.write_value([1u8, 2, 3, 4]);
+    alloc.resize_bytes(32);
+    assert_eq!(alloc.layout(), Layout::from_size_align(32, 4).unwrap());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 4]>() }, [1, 2, 3, 4]);
+    alloc.resize_bytes(2);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 2]>() }, [1, 2]);
+    alloc.resize_bytes(0);
+    assert_eq!(alloc.layout(), Layout::from_size_align(0, 4).unwrap());
+    assert_eq!(alloc.as_ptr::<u8>(), NonNull::<u32>::dangling().cast());
+
+    let mut alloc = Allocation::new(Layout::from_size_align(2, 2).unwrap());
+    alloc.write_value([7u8, 8]);
+    alloc.resize_bytes_zeroed(8);
+    assert_eq!(
+        unsafe { alloc.read_value::<[u8; 8]>() },
+        [7, 8, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn realloc_zero_sized() {
+    let counting = Counting::default();
+    let mut alloc = Allocation::new_in(Layout::from_size_align(16, 8).unwrap(), &counting);
+    alloc.write_value([3u64, 4]);
+    counting.calls.set(0);
+    alloc.realloc(Layout::from_size_align(0, 8).unwrap());
+    // one call to allocate the zero-sized block, one call to deallocate the old block
+    assert_eq!(counting.calls.get(), 2);
+    assert_eq!(alloc.layout(), Layout::from_size_align(0, 8).unwrap());
+    assert_eq!(alloc.as_ptr::<u64>(), NonNull::dangling());
+    alloc.realloc(Layout::from_size_align(16, 8).unwrap());
+    alloc.write_value([5u64, 6]);
+    assert_eq!(unsafe { alloc.read_value::<[u64; 2]>() }, [5, 6]);
+
+    // shrinking to zero takes the same path instead of calling `shrink`
+    let mut alloc = Allocation::new_in(Layout::from_size_align(16, 8).unwrap(), &counting);
+    counting.calls.set(0);
+    alloc.shrink(Layout::from_size_align(0, 8).unwrap());
+    assert_eq!(counting.calls.get(), 2);
+    assert_eq!(alloc.as_ptr::<u64>(), NonNull::dangling());
+    drop(alloc);
+
+    let mut alloc = Allocation::new(Layout::new::<u32>());
+    alloc.realloc_zeroed(Layout::new::<[u32; 0]>());
+    alloc.realloc_zeroed(Layout::new::<[u32; 2]>());
+    assert_eq!(unsafe { alloc.read_value::<[u32; 2]>() }, [0, 0]);
+}
+
+#[test]
+fn realloc_for() {
+    let mut alloc = Allocation::new(Layout::new::<u8>());
+    alloc.realloc_for::<[i32; 4]>();
+    assert_eq!(alloc.layout(), Layout::new::<[i32; 4]>());
+    alloc.write_value([1i32, -2, 3, -4]);
+    alloc.realloc_array_for::<i32>(8).unwrap();
+    assert_eq!(alloc.layout(), Layout::new::<[i32; 8]>());
+    assert_eq!(unsafe { alloc.read_value::<[i32; 4]>() }, [1, -2, 3, -4]);
+    assert!(alloc.realloc_array_for::<i32>(usize::MAX).is_err());
+    assert_eq!(alloc.layout(), Layout::new::<[i32; 8]>());
+}
+
+#[test]
+fn align_to() {
+    let mut alloc = Allocation::new(Layout::from_size_align(4, 4).unwrap());
+    alloc.write_value(0x1234_5678u32);
+    alloc.align_to(64);
+    assert_eq!(alloc.layout(), Layout::from_size_align(4, 64).unwrap());
+    assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % 64, 0);
+    assert_eq!(unsafe { alloc.read_value::<u32>() }, 0x1234_5678);
+    // never lowers the alignment
+    alloc.realloc(Layout::from_size_align(2, 8).unwrap());
+    alloc.align_to(8);
+    assert_eq!(alloc.layout().align(), 64);
+    assert_eq!(
+        alloc.requested_layout(),
+        Layout::from_size_align(2, 8).unwrap()
+    );
+}
+
+#[test]
+#[should_panic = "alignment must be a power of two"]
+fn align_to_invalid() {
+    let mut alloc = Allocation::new(Layout::from_size_align(4, 4).unwrap());
+    alloc.align_to(3);
+}
+
+#[test]
+fn try_grow_in_place() {
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 4]>(), OverAllocating);
+    let ptr = alloc.as_ptr::<u8>();
+    alloc.try_grow_in_place(Layout::new::<[u8; 64]>()).unwrap();
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(alloc.requested_layout(), Layout::new::<[u8; 64]>());
+
+    assert!(alloc.try_grow_in_place(Layout::new::<[u8; 65]>()).is_err());
+    assert!(alloc
+        .try_grow_in_place(Layout::from_size_align(4, 4).unwrap())
+        .is_err());
+    assert_eq!(alloc.requested_layout(), Layout::new::<[u8; 64]>());
+    assert_eq!(alloc.layout().size(), 64);
+}
+
+#[test]
+fn into_boxed_bytes() {
+    let mut alloc = Allocation::new(Layout::from_size_align(8, 4).unwrap());
+    alloc.write_value([9u8, 8, 7, 6, 5, 4, 3, 2]);
+    let boxed = unsafe { alloc.into_boxed_bytes() };
+    assert_eq!(&*boxed, &[9, 8, 7, 6, 5, 4, 3, 2]);
+    let alloc = Allocation::from(boxed);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 8]>());
+
+    let alloc = Allocation::new(Layout::new::<[u16; 3]>());
+    let boxed = alloc.into_boxed_uninit_bytes();
+    assert_eq!(boxed.len(), 6);
+}
+
+#[cfg(feature = "libc")]
+#[test]
+fn malloc() {
+    let mut alloc = Allocation::new_in(Layout::new::<[u32; 4]>(), Malloc);
+    alloc.write_value([1u32, 2, 3, 4]);
+    alloc.realloc(Layout::new::<[u32; 64]>());
+    assert_eq!(unsafe { alloc.read_value::<[u32; 4]>() }, [1, 2, 3, 4]);
+    alloc.realloc(Layout::new::<[u32; 0]>());
+    drop(alloc);
+
+    let alloc = Allocation::zeroed_in(Layout::new::<[u64; 8]>(), Malloc);
+    assert_eq!(unsafe { alloc.read_value::<[u64; 8]>() }, [0; 8]);
+
+    // alignments `malloc` does not guarantee for the size are served by `posix_memalign`
+    #[cfg(unix)]
+    for layout in [
+        Layout::from_size_align(64, 4096).unwrap(),
+        Layout::from_size_align(1, 16).unwrap(),
+    ] {
+        let mut alloc = Allocation::zeroed_in(layout, Malloc);
+        assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % layout.align(), 0);
+        assert_eq!(unsafe { alloc.read_value::<u8>() }, 0);
+        alloc.write_value(7u8);
+        alloc.realloc(Layout::from_size_align(128, layout.align()).unwrap());
+        assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % layout.align(), 0);
+        assert_eq!(unsafe { alloc.read_value::<u8>() }, 7);
+    }
+}
+
+#[test]
+fn tracking_allocator() {
+    let tracking = TrackingAllocator::new(Global);
+    let mut first = Allocation::new_in(Layout::new::<[u8; 16]>(), &tracking);
+    let second = Allocation::zeroed_in(Layout::new::<[u8; 8]>(), &tracking);
+    assert_eq!(tracking.bytes_allocated(), 24);
+    assert_eq!(tracking.live_allocations(), 2);
+
+    first.realloc(Layout::new::<[u8; 64]>());
+    assert_eq!(tracking.bytes_allocated(), 72);
+    first.shrink(Layout::new::<[u8; 32]>());
+    assert_eq!(tracking.bytes_allocated(), 40);
+
+    drop(first);
+    assert_eq!(tracking.bytes_allocated(), 8);
+    assert_eq!(tracking.live_allocations(), 1);
+    drop(second);
+    assert_eq!(tracking.bytes_allocated(), 0);
+    assert_eq!(tracking.live_allocations(), 0);
+
+    // over-allocation of the wrapped allocator is not counted
+    let tracking = TrackingAllocator::new(OverAllocating);
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 10]>(), &tracking);
+    assert_eq!(tracking.bytes_allocated(), 10);
+    alloc.realloc(Layout::new::<[u8; 100]>());
+    assert_eq!(tracking.bytes_allocated(), 100);
+    drop(alloc);
+    assert_eq!(tracking.bytes_allocated(), 0);
+}
+
+#[test]
+fn borrowed_allocator() {
+    let tracking = TrackingAllocator::new(Global);
+    let first = Allocation::new_in(Layout::new::<u64>(), &tracking);
+    let second = Allocation::new_in(Layout::new::<u64>(), &tracking);
+    assert_eq!(tracking.live_allocations(), 2);
+    drop((first, second));
+    // the allocator was only borrowed
+    let _ = tracking.into_inner();
+}
+
+#[test]
+fn dyn_allocator() {
+    let tracking = TrackingAllocator::new(Global);
+    let allocators: [&dyn Allocator; 2] = [&Global, &tracking];
+    for allocator in allocators {
+        let mut alloc = Allocation::new_in(Layout::new::<u32>(), allocator);
+        alloc.write_value(5u32);
+        alloc.realloc(Layout::new::<[u32; 8]>());
+        assert_eq!(unsafe { alloc.read_value::<u32>() }, 5);
+    }
+    assert_eq!(tracking.live_allocations(), 0);
+}
+
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn convert_api2() {
+    let tracking = TrackingAllocator::new(Global);
+    let alloc = Allocation::new_in(Layout::array::<u32>(4).unwrap(), &tracking);
+    let mut vec = alloc.try_into_api2_vec::<u32>().unwrap();
+    vec.extend(0..8);
+    assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7]);
+    let alloc = Allocation::from(vec);
+    assert_eq!(alloc.layout(), Layout::array::<u32>(8).unwrap());
+    drop(alloc);
+    assert_eq!(tracking.live_allocations(), 0);
+
+    let alloc = Allocation::from_value_in(42u64, &tracking);
+    let boxed = alloc.try_into_api2_box::<u64>().unwrap();
+    let boxed = unsafe { boxed.assume_init() };
+    assert_eq!(*boxed, 42);
+    let alloc = Allocation::from(boxed);
+    assert_eq!(alloc.layout(), Layout::new::<u64>());
+    drop(alloc);
+    assert_eq!(tracking.live_allocations(), 0);
+}
+
+#[test]
+fn bump() {
+    let mut buffer = [core::mem::MaybeUninit::uninit(); 64];
+    let bump = Bump::new(&mut buffer);
+
+    let first = Allocation::new_in(Layout::new::<u8>(), &bump);
+    let mut second = Allocation::new_in(Layout::new::<u64>(), &bump);
+    assert_eq!(second.as_ptr::<u8>().as_ptr().addr() % 8, 0);
+    second.write_value(7u64);
+    // the most recent allocation can grow in place
+    second.realloc(Layout::new::<[u64; 2]>());
+    assert_eq!(unsafe { second.read_value::<u64>() }, 7);
+    let used = bump.used();
+    assert!(used <= 24);
+
+    // older allocations can not
+    let mut first = first;
+    assert!(first.try_realloc(Layout::new::<[u8; 2]>()).is_err());
+
+    assert!(Allocation::try_new_in(Layout::new::<[u8; 64]>(), &bump).is_err());
+    let rest = Allocation::new_in(Layout::array::<u8>(64 - used).unwrap(), &bump);
+    assert_eq!(bump.used(), 64);
+    assert!(Allocation::try_new_in(Layout::new::<u8>(), &bump).is_err());
+    drop((first, second, rest));
+}
+
+#[test]
+fn bump_zero_sized_not_last() {
+    let mut buffer = [core::mem::MaybeUninit::uninit(); 64];
+    let bump = Bump::new(&mut buffer);
+
+    let empty = bump.allocate(Layout::new::<()>()).unwrap();
+    let live = bump.allocate(Layout::new::<u8>()).unwrap();
+    // both blocks start at the same offset, but only the second one is at the end
+    assert_eq!(empty.cast::<u8>(), live.cast::<u8>());
+    let grown = unsafe { bump.grow(empty.cast(), Layout::new::<()>(), Layout::new::<[u8; 4]>()) };
+    assert!(grown.is_err());
+    let grown = unsafe { bump.grow(live.cast(), Layout::new::<u8>(), Layout::new::<[u8; 4]>()) };
+    assert!(grown.is_ok());
+}
+
+#[test]
+fn realloc_zeroed_changing_alignment() {
+    // Changing the alignment takes the allocate-and-copy path of the allocator, which has to
+    // preserve the prefix and zero the whole tail of the new block.
+    let mut alloc = Allocation::new(Layout::from_size_align(8, 1).unwrap());
+    alloc.write_value([0xffu8; 8]);
+    alloc.realloc_zeroed(Layout::from_size_align(64, 64).unwrap());
+    assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % 64, 0);
+    let bytes = unsafe { alloc.read_value::<[u8; 64]>() };
+    assert_eq!(bytes[..8], [0xff; 8]);
+    assert_eq!(bytes[8..], [0; 56]);
+}
+
+#[test]
+fn niche() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Allocation>();
+    assert_eq!(size_of::<Option<Allocation>>(), size_of::<Allocation>());
+}
+
+#[test]
+fn global_is_clone_default() {
+    // The re-exported `Global` already derives these, both from allocator-api2 and from std
+    fn allocate_twice<A: Allocator + Clone + Default + core::fmt::Debug>() -> [Allocation<A>; 2] {
+        let alloc = A::default();
+        let _ = alloc::format!("{alloc:?}");
+        [
+            Allocation::new_in(Layout::new::<u32>(), alloc.clone()),
+            Allocation::new_in(Layout::new::<u32>(), alloc),
+        ]
+    }
+    let _ = allocate_twice::<Global>();
+}
+
+#[test]
+fn secure_allocation() {
+    /// Checks that all bytes are zeroed when deallocating.
+    #[derive(Default)]
+    struct ZeroCheck {
+        checked: Cell<bool>,
+    }
+    unsafe impl Allocator for &ZeroCheck {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            let bytes = unsafe { core::slice::from_raw_parts(ptr.as_ptr(), layout.size()) };
+            assert!(bytes.iter().all(|&b| b == 0));
+            self.checked.set(true);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let zero_check = ZeroCheck::default();
+    let mut secure = SecureAllocation::new_in(Layout::new::<[u8; 32]>(), &zero_check);
+    for byte in secure.as_bytes_mut() {
+        byte.write(0xa5);
+    }
+    assert_eq!(secure.as_bytes().len(), 32);
+    drop(secure);
+    assert!(zero_check.checked.get());
+}
+
+#[test]
+fn leak() {
+    let layout = Layout::new::<[u32; 4]>();
+    let leaked = Allocation::new(layout).leak();
+    assert_eq!(leaked.len(), layout.size());
+    leaked[0].write(1);
+    // Reclaim the memory again to keep leak checkers quiet
+    let ptr = NonNull::new(leaked.as_mut_ptr().cast::<u8>()).unwrap();
+    drop(unsafe { Allocation::from_parts(ptr, layout) });
+
+    let buffer = &mut [core::mem::MaybeUninit::uninit(); 64];
+    let bump = Bump::new(buffer);
+    let leaked = Allocation::new_in(Layout::new::<[u8; 24]>(), &bump).leak_in();
+    assert_eq!(leaked.len(), 24);
+}
+
+#[test]
+fn as_ref_bytes() {
+    use core::mem::MaybeUninit;
+    fn fill(mut bytes: impl AsMut<[MaybeUninit<u8>]>) -> usize {
+        let bytes = bytes.as_mut();
+        for byte in bytes.iter_mut() {
+            byte.write(0x11);
+        }
+        bytes.len()
+    }
+    fn count(bytes: impl AsRef<[MaybeUninit<u8>]>) -> usize {
+        bytes.as_ref().len()
+    }
+
+    let mut alloc = Allocation::new(Layout::new::<[u8; 12]>());
+    assert_eq!(fill(&mut alloc), 12);
+    assert_eq!(count(&alloc), 12);
+    assert_eq!(unsafe { alloc.as_bytes()[11].assume_init() }, 0x11);
+}
+
+#[test]
+fn conversion_error_display() {
+    use alloc::string::ToString;
+    let err = Allocation::new(Layout::new::<u16>())
+        .try_into_box::<u32>()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "allocation layout (size 2, align 2) does not match expected (size 4, align 4)"
+    );
+
+    let err = Allocation::new(Layout::new::<u16>())
+        .try_into_vec::<u32>()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "allocation alignment 2 does not match expected element alignment 4"
+    );
+
+    let err = Allocation::new(Layout::new::<[u8; 5]>())
+        .try_into_vec::<[u8; 2]>()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "allocated size 5 is not a multiple of the element size 2"
+    );
+
+    let err: Box<dyn core::error::Error> = err.into();
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn vec_capacity_overflow() {
+    let alloc = Allocation::new(Layout::new::<[u32; 4]>());
+    let err = alloc
+        .try_into_vec_with_capacity::<u32>(usize::MAX / 2)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        VecConversionError::CapacityOverflow {
+            element_size: 4,
+            capacity
+        } if capacity == usize::MAX / 2
+    ));
+
+    let alloc = Allocation::new(Layout::new::<[u32; 4]>());
+    assert!(alloc.try_into_vec_with_capacity::<u32>(3).is_err());
+    let alloc = Allocation::new(Layout::new::<[u32; 4]>());
+    let vec = alloc.try_into_vec_with_capacity::<u32>(4).unwrap();
+    assert_eq!(vec.capacity(), 4);
+}
+
+#[test]
+fn convert_string() {
+    use alloc::string::String;
+    let mut text = String::with_capacity(16);
+    text.push_str("hello");
+    let alloc = Allocation::from(text);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 16]>());
+
+    let mut text = alloc.try_into_string().unwrap();
+    assert!(text.is_empty());
+    assert_eq!(text.capacity(), 16);
+    // The bytes written before are still in the buffer
+    unsafe { text.as_mut_vec().set_len(5) };
+    assert_eq!(text, "hello");
+}
+
+#[test]
+fn convert_cstring() {
+    use alloc::ffi::CString;
+    let text = CString::new("hi").unwrap();
+    let ptr = text.as_ptr();
+    let alloc = Allocation::from(text);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 3]>());
+
+    let text = unsafe { alloc.try_into_cstring() }.unwrap();
+    assert_eq!(text.as_ptr(), ptr);
+    assert_eq!(text.as_bytes(), b"hi");
+
+    let mut alloc = Allocation::new(Layout::new::<[u8; 3]>());
+    alloc.write_value(*b"h\0i");
+    assert!(unsafe { alloc.try_into_cstring() }.is_err());
+}
+
+#[test]
+fn into_box_assume_init() {
+    let alloc = Allocation::from_value(7u32);
+    let boxed: Box<u32> = unsafe { alloc.into_box_assume_init() }.unwrap();
+    assert_eq!(*boxed, 7);
+
+    let alloc = Allocation::from_value(7u32);
+    assert!(unsafe { alloc.into_box_assume_init::<u64>() }.is_err());
+}
+
+#[test]
+fn convert_array_box() {
+    let alloc = Allocation::new(Layout::new::<[u16; 4]>());
+    let mut array = alloc.try_into_array_box::<u16, 4>().unwrap();
+    for (i, elem) in array.iter_mut().enumerate() {
+        elem.write(i as u16 * 2);
+    }
+    let array: Box<[u16; 4]> = unsafe { Box::from_raw(Box::into_raw(array).cast()) };
+    assert_eq!(*array, [0, 2, 4, 6]);
+
+    let alloc = Allocation::from(array);
+    assert_eq!(alloc.layout(), Layout::new::<[u16; 4]>());
+    assert!(alloc.try_into_array_box::<u16, 3>().is_err());
+}
+
+#[test]
+fn into_vec_with_len() {
+    let mut alloc = Allocation::new(Layout::new::<[i32; 4]>());
+    alloc.write_value([1, 2, 3]);
+    let vec = unsafe { alloc.into_vec_with_len::<i32>(3) }.unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.capacity(), 4);
+
+    let alloc = Allocation::new(Layout::new::<[i32; 4]>());
+    let err = unsafe { alloc.into_vec_with_len::<i32>(5) }.unwrap_err();
+    assert!(matches!(
+        err,
+        VecConversionError::LengthExceedsCapacity {
+            len: 5,
+            capacity: 4
+        }
+    ));
+}
+
+#[test]
+fn assume_fits_into_box() {
+    let alloc = Allocation::from_value(11u64);
+    let boxed = unsafe { alloc.assume_fits_into_box::<u64>() };
+    assert_eq!(unsafe { boxed.assume_init() }, Box::new(11));
+
+    let checked = Allocation::from_value(11u64).try_into_box::<u64>().unwrap();
+    assert_eq!(unsafe { checked.assume_init() }, Box::new(11));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic = "allocation layout does not match"]
+fn assume_fits_into_box_mismatch() {
+    let alloc = Allocation::from_value(11u64);
+    let _ = unsafe { alloc.assume_fits_into_box::<u32>() };
+}
+
+#[test]
+fn copy_to() {
+    let tracking = TrackingAllocator::new(Global);
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 8]>(), &tracking);
+    alloc.write_value(*b"migrated");
+
+    let alloc = alloc.copy_to(Global).unwrap();
+    assert_eq!(tracking.live_allocations(), 0);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"migrated");
+
+    let alloc = alloc.copy_to(&tracking).unwrap();
+    assert_eq!(tracking.live_allocations(), 1);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"migrated");
+    drop(alloc);
+
+    let empty = Allocation::new_in(Layout::new::<()>(), &tracking);
+    let empty = empty.copy_to(Global).unwrap();
+    assert_eq!(empty.layout(), Layout::new::<()>());
+}
+
+#[test]
+fn into_global() {
+    let tracking = TrackingAllocator::new(Global);
+    let alloc = Allocation::from_value_in(0xdead_beef_u32, &tracking);
+    assert_eq!(tracking.live_allocations(), 1);
+
+    let alloc: Allocation = alloc.into_global();
+    assert_eq!(tracking.live_allocations(), 0);
+    assert_eq!(unsafe { alloc.read_value::<u32>() }, 0xdead_beef);
+
+    let alloc = Allocation::from_global_in(alloc, &tracking);
+    assert_eq!(tracking.live_allocations(), 1);
+    assert_eq!(unsafe { alloc.read_value::<u32>() }, 0xdead_beef);
+}
+
+#[test]
+fn split_at() {
+    let tracking = TrackingAllocator::new(Global);
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 16]>(), &tracking);
+    alloc.write_value(*b"headpayload12345");
+
+    let Ok((head, tail)) = alloc.split_at(4) else {
+        panic!("failed to split the allocation")
+    };
+    assert_eq!(tracking.live_allocations(), 2);
+    assert_eq!(head.layout().size(), 4);
+    assert_eq!(tail.layout().size(), 12);
+    assert_eq!(unsafe { head.read_value::<[u8; 4]>() }, *b"head");
+    assert_eq!(unsafe { tail.read_value::<[u8; 12]>() }, *b"payload12345");
+}
+
+#[test]
+fn append() {
+    let mut first = Allocation::from_value(*b"con");
+    let second = Allocation::from_value(*b"catenated");
+    let Ok(()) = first.append(second) else {
+        panic!("failed to append")
+    };
+    assert_eq!(first.len(), 12);
+    assert_eq!(unsafe { first.read_value::<[u8; 12]>() }, *b"concatenated");
+
+    let third = Allocation::from_value(7u32);
+    let Ok(()) = first.append(third) else {
+        panic!("failed to append")
+    };
+    assert_eq!(first.layout(), Layout::from_size_align(16, 4).unwrap());
+    assert_eq!(unsafe { first.as_ptr::<u32>().add(3).read() }, 7);
+}
+
+#[test]
+fn index_bytes() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 8]>());
+    for (i, byte) in alloc[..].iter_mut().enumerate() {
+        byte.write(i as u8);
+    }
+    assert_eq!(alloc[4..8].len(), 4);
+    assert_eq!(unsafe { alloc[4..8][0].assume_init() }, 4);
+    assert_eq!(alloc[..3].len(), 3);
+    assert_eq!(unsafe { alloc[6..][1].assume_init() }, 7);
+    alloc[2..4][0].write(42);
+    assert_eq!(unsafe { alloc[..][2].assume_init() }, 42);
+}
+
+#[test]
+#[should_panic]
+fn index_bytes_out_of_bounds() {
+    let alloc = Allocation::new(Layout::new::<[u8; 8]>());
+    let _ = &alloc[4..9];
+}
+
+#[test]
+fn content_eq() {
+    let first = Allocation::from_value(*b"same");
+    let second = Allocation::from_value(u32::from_ne_bytes(*b"same"));
+    assert!(unsafe { first.content_eq(&second) });
+
+    let different = Allocation::from_value(*b"diff");
+    assert!(!unsafe { first.content_eq(&different) });
+
+    let longer = Allocation::from_value(*b"same!");
+    assert!(!unsafe { first.content_eq(&longer) });
+}
+
+#[test]
+fn copy_within() {
+    let mut alloc = Allocation::from_value(*b"abcdefgh");
+    alloc.copy_within(0..6, 2);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"ababcdef");
+    alloc.copy_within(3..8, 0);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"bcdefdef");
+}
+
+#[test]
+#[should_panic]
+fn copy_within_out_of_bounds() {
+    let mut alloc = Allocation::from_value(*b"abcdefgh");
+    alloc.copy_within(0..4, 5);
+}
+
+#[test]
+fn for_value() {
+    let values: &[u32] = &[1, 2, 3, 4, 5];
+    let alloc = Allocation::for_value(values);
+    assert_eq!(alloc.layout(), Layout::new::<[u32; 5]>());
+
+    let alloc = Allocation::for_value_in("text", Global);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 4]>());
+}
+
+#[test]
+fn new_array() {
+    let alloc = Allocation::new_array::<i32>(8);
+    assert_eq!(alloc.layout(), Layout::new::<[i32; 8]>());
+    assert_eq!(alloc.capacity_for::<i32>(), 8);
+
+    let alloc = Allocation::zeroed_array::<u16>(3);
+    assert_eq!(unsafe { alloc.read_value::<[u16; 3]>() }, [0; 3]);
+
+    assert!(Allocation::new_array_checked::<i32>(usize::MAX / 2).is_err());
+    assert!(Allocation::try_zeroed_array_in::<u64>(usize::MAX / 4, Global).is_err());
+}
+
+#[test]
+#[should_panic = "capacity overflow"]
+fn new_array_overflow() {
+    let _ = Allocation::new_array::<i32>(usize::MAX / 2);
+}
+
+#[test]
+fn dangling() {
+    let alloc = Allocation::dangling::<u64>();
+    assert_eq!(alloc.layout(), Layout::from_size_align(0, 8).unwrap());
+    assert!(alloc.as_ptr::<u64>().as_ptr().is_aligned());
+
+    let mut alloc = Allocation::dangling::<u16>();
+    alloc.realloc(Layout::new::<[u16; 2]>());
+    alloc.write_value([1u16, 2]);
+}
+
+#[test]
+fn default() {
+    let alloc = Allocation::default();
+    assert_eq!(alloc.layout(), Layout::new::<()>());
+
+    let mut alloc = Allocation::new_for::<u32>();
+    let taken = core::mem::take(&mut alloc);
+    assert_eq!(taken.layout(), Layout::new::<u32>());
+    assert_eq!(alloc.layout().size(), 0);
+}
+
+#[test]
+fn init_with() {
+    struct Pair {
+        first: u32,
+        second: u16,
+    }
+    let mut alloc = Allocation::new_for::<Pair>();
+    let pair = unsafe {
+        alloc.init_with(|uninit: &mut core::mem::MaybeUninit<Pair>| {
+            let ptr = uninit.as_mut_ptr();
+            core::ptr::addr_of_mut!((*ptr).first).write(1);
+            core::ptr::addr_of_mut!((*ptr).second).write(2);
+        })
+    };
+    assert_eq!((pair.first, pair.second), (1, 2));
+}
+
+#[test]
+fn write_slice() {
+    let mut alloc = Allocation::new_array::<u32>(4);
+    let written = alloc.write_slice(&[1u32, 2, 3]);
+    assert_eq!(written, [1, 2, 3]);
+    written[2] = 4;
+    assert_eq!(unsafe { alloc.as_ptr::<u32>().add(2).read() }, 4);
+}
+
+#[test]
+#[should_panic = "allocation does not fit"]
+fn write_slice_too_long() {
+    let mut alloc = Allocation::new_array::<u32>(2);
+    alloc.write_slice(&[1u32, 2, 3]);
+}
+
+#[test]
+fn grow_zeroed() {
+    let mut alloc = Allocation::from_value(*b"keep");
+    alloc.grow_zeroed(Layout::new::<[u8; 8]>());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"keep\0\0\0\0");
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), 1);
+}
+
+#[test]
+fn usable_size() {
+    let alloc = Allocation::new_in(Layout::new::<[u8; 10]>(), OverAllocating);
+    assert_eq!(alloc.requested_size(), 10);
+    assert_eq!(alloc.usable_size(), 64);
+    assert!(alloc.usable_size() >= alloc.requested_size());
+
+    let alloc = Allocation::new(Layout::new::<[u8; 10]>());
+    assert_eq!(alloc.requested_size(), alloc.usable_size());
+}
+
+#[test]
+fn reinterpret() {
+    let alloc = Allocation::new(Layout::from_size_align(18, 4).unwrap());
+    assert_eq!(alloc.reinterpret::<u32>().len(), 4);
+    assert_eq!(alloc.reinterpret::<u8>().len(), 18);
+    assert_eq!(alloc.reinterpret::<()>().len(), 0);
+}
+
+#[test]
+#[should_panic = "allocation not aligned"]
+fn reinterpret_misaligned() {
+    let alloc = Allocation::new(Layout::new::<[u8; 16]>());
+    let _ = alloc.reinterpret::<u64>();
+}
+
+#[test]
+fn c_void() {
+    let alloc = Allocation::from_value(5u16);
+    let (ptr, layout, alloc_) = alloc.into_parts_with_alloc();
+    let ptr: *mut core::ffi::c_void = ptr.as_ptr().cast();
+    let alloc = unsafe { Allocation::from_c_void(ptr, layout, alloc_) };
+    assert_eq!(alloc.as_c_void(), ptr);
+    assert_eq!(unsafe { alloc.read_value::<u16>() }, 5);
+}
+
+#[test]
+fn raw_slice() {
+    let layout = Layout::from_size_align(24, 8).unwrap();
+    let block = Global.allocate(layout).unwrap();
+    let alloc = unsafe { Allocation::from_raw_slice_in(block, 8, Global) };
+    assert_eq!(alloc.layout(), layout);
+
+    let (slice, alloc_) = alloc.into_raw_nonnull_slice();
+    assert_eq!(slice, block);
+    let alloc = unsafe { Allocation::from_raw_slice_in(slice, 8, alloc_) };
+    assert_eq!(alloc.as_slice().len(), 24);
+}
+
+#[test]
+fn zero_sized() {
+    let layout = Layout::from_size_align(0, 4).unwrap();
+    let mut alloc = Allocation::new(layout);
+    assert!(alloc.is_zero_sized());
+    assert!(alloc.as_ptr::<u32>().as_ptr().is_aligned());
+    assert!(alloc.as_bytes().is_empty());
+    assert!(alloc.as_bytes_mut().is_empty());
+    assert!(alloc[..].is_empty());
+    assert_eq!(alloc.as_slice().len(), 0);
+    assert_eq!(alloc.reinterpret::<u32>().len(), 0);
+    assert_eq!(alloc.capacity_for::<u32>(), 0);
+    assert!(!alloc.contains_ptr(alloc.as_ptr::<u8>().as_ptr()));
+    assert!(alloc.fits::<()>());
+    alloc.write_value(());
+
+    let copy = Allocation::new(layout);
+    assert!(unsafe { alloc.content_eq(&copy) });
+    let copy = copy.copy_to(Global).unwrap();
+    assert!(copy.is_zero_sized());
+
+    alloc.realloc(Layout::new::<u32>());
+    assert!(!alloc.is_zero_sized());
+    alloc.realloc(layout);
+    assert!(alloc.is_zero_sized());
+
+    let boxed = Allocation::new(Layout::new::<()>())
+        .try_into_box::<()>()
+        .unwrap();
+    let alloc = Allocation::from(boxed);
+    assert!(alloc.is_zero_sized());
+    assert!(matches!(
+        alloc.try_into_vec::<()>(),
+        Err(VecConversionError::ZeroSizedElements)
+    ));
+    let bytes = Allocation::new(layout).into_boxed_uninit_bytes();
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn grow_elements() {
+    let mut alloc = Allocation::new_array::<i32>(4);
+    alloc.write_value([1, 2, 3, 4]);
+    alloc.try_grow_elements::<i32>(2).unwrap();
+    assert_eq!(alloc.capacity_for::<i32>(), 6);
+    assert_eq!(unsafe { alloc.read_value::<[i32; 4]>() }, [1, 2, 3, 4]);
+
+    assert!(alloc.try_grow_elements::<i32>(usize::MAX).is_err());
+    assert_eq!(alloc.capacity_for::<i32>(), 6);
+    // zero-sized elements can not grow the allocation and must not free it
+    assert!(alloc.try_grow_elements::<()>(2).is_err());
+    assert_eq!(alloc.capacity_for::<i32>(), 6);
+
+    // a stricter alignment of the allocation is kept
+    let mut alloc = Allocation::new(Layout::from_size_align(8, 16).unwrap());
+    alloc.try_grow_elements::<u8>(8).unwrap();
+    assert_eq!(alloc.align(), 16);
+    assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % 16, 0);
+    assert!(alloc.byte_capacity() >= 16);
+
+    // an under-aligned allocation counts its elements by size and never shrinks
+    let mut alloc = Allocation::new(Layout::from_size_align(64, 1).unwrap());
+    alloc.try_grow_elements::<u32>(1).unwrap();
+    assert_eq!(alloc.requested_layout(), Layout::array::<u32>(17).unwrap());
+    assert!(alloc.byte_capacity() >= 68);
+    let mut alloc = Allocation::new(Layout::from_size_align(65, 1).unwrap());
+    assert!(alloc.try_grow_elements::<u32>(0).is_err());
+    assert_eq!(alloc.layout(), Layout::from_size_align(65, 1).unwrap());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck_views() {
+    let mut alloc = Allocation::new_array::<u32>(4);
+    assert_eq!(alloc.fill_zeroable::<u32>(), [0; 4]);
+    unsafe { alloc.as_pod_slice_mut::<u32>() }.copy_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(unsafe { alloc.as_pod_slice::<u32>() }, [1, 2, 3, 4]);
+    assert_eq!(unsafe { alloc.as_pod_slice::<u16>() }.len(), 8);
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn zerocopy_views() {
+    #[derive(zerocopy::FromBytes, zerocopy::Immutable, PartialEq, Debug, Clone, Copy)]
+    #[repr(C)]
+    struct Header {
+        tag: u32,
+        len: u32,
+    }
+    let header = Header { tag: 7, len: 12 };
+    let alloc = Allocation::from_value(header);
+    assert_eq!(unsafe { alloc.try_as_ref::<Header>() }, Some(&header));
+    assert_eq!(unsafe { alloc.try_as_ref::<[Header; 2]>() }, None);
+    assert_eq!(unsafe { alloc.try_as_slice::<u32>() }, Some(&[7, 12][..]));
+    assert_eq!(unsafe { alloc.try_as_slice::<u64>() }, None);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes_mut() {
+    use bytes::BufMut;
+    let alloc = Allocation::new_array::<u8>(32);
+    let ptr = alloc.as_ptr::<u8>();
+    let mut bytes = alloc.into_bytes_mut();
+    assert!(bytes.is_empty());
+    assert_eq!(bytes.capacity(), 32);
+    assert_eq!(bytes.as_ptr(), ptr.as_ptr().cast_const());
+    bytes.put_slice(b"payload");
+
+    let alloc = Allocation::from_bytes_mut(bytes);
+    assert_eq!(alloc.as_ptr::<u8>(), ptr);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 32]>());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 7]>() }, *b"payload");
+
+    let empty = Allocation::new(Layout::new::<()>()).into_bytes_mut();
+    assert_eq!(empty.capacity(), 0);
+}
+
+#[test]
+fn from_iter() {
+    let alloc: Allocation = (0..4).collect();
+    assert!(alloc.len() >= 4);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 4]>() }, [0, 1, 2, 3]);
+}
+
+#[test]
+fn from_slice() {
+    let alloc = Allocation::try_from_slice(b"hello").unwrap();
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 5]>());
+    assert!(unsafe { alloc.content_eq(&Allocation::from_value(*b"hello")) });
+
+    let alloc = Allocation::from_slice(b"");
+    assert!(alloc.is_zero_sized());
+}
+
+#[test]
+fn into_pinned_box() {
+    struct Node {
+        value: u32,
+        _pinned: core::marker::PhantomPinned,
+    }
+    let alloc = Allocation::from_value(Node {
+        value: 3,
+        _pinned: core::marker::PhantomPinned,
+    });
+    let pinned = unsafe { alloc.into_pinned_box::<Node>() }.unwrap();
+    assert_eq!(pinned.value, 3);
+
+    let alloc = Allocation::from_value(3u32);
+    assert!(unsafe { alloc.into_pinned_box::<u64>() }.is_err());
+}
+
+#[test]
+fn conversion_error() {
+    use alloc::string::ToString;
+    use core::error::Error;
+    fn convert(alloc: Allocation, as_box: bool) -> Result<(), ConversionError> {
+        if as_box {
+            alloc.try_into_box::<u64>()?;
+        } else {
+            alloc.try_into_vec::<u64>()?;
+        }
+        Ok(())
+    }
+    let err = convert(Allocation::new_for::<u32>(), true).unwrap_err();
+    assert!(matches!(err, ConversionError::Box(_)));
+    assert!(err.source().is_some());
+    let err = convert(Allocation::new_for::<u32>(), false).unwrap_err();
+    assert!(matches!(err, ConversionError::Vec(_)));
+    assert_eq!(
+        err.to_string(),
+        "failed to convert the allocation into a vec"
+    );
+}
+
+#[test]
+fn realloc_moved() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 64]>());
+    assert!(!alloc.try_realloc_moved(Layout::new::<[u8; 64]>()).unwrap());
+    assert!(!alloc.try_realloc_moved(Layout::new::<[u8; 32]>()).unwrap());
+
+    // Request an alignment no allocator hands out for a 64 byte block by accident
+    let align = 1 << 16;
+    let moved = alloc
+        .try_realloc_moved(Layout::from_size_align(64, align).unwrap())
+        .unwrap();
+    assert!(moved);
+    assert_eq!(alloc.as_ptr::<u8>().as_ptr().addr() % align, 0);
+}
+
+#[test]
+fn try_clone_in() {
+    let tracking = TrackingAllocator::new(Global);
+    let alloc = Allocation::from_value(*b"cloned");
+    let clone = alloc.try_clone_in(&tracking).unwrap();
+    assert_eq!(tracking.live_allocations(), 1);
+    assert!(unsafe { clone.content_eq(&alloc) });
+    assert_ne!(clone.as_ptr::<u8>(), alloc.as_ptr::<u8>());
+    drop(clone);
+    assert_eq!(tracking.live_allocations(), 0);
+}
+
+#[test]
+fn init_allocation() {
+    let mut buffer = InitAllocation::new(Layout::new::<[u16; 2]>());
+    assert!(buffer.is_empty());
+    buffer.push_bytes(&1u16.to_ne_bytes());
+    buffer.push_bytes(&2u16.to_ne_bytes());
+    buffer.push_bytes(&3u16.to_ne_bytes());
+    assert_eq!(buffer.len(), 6);
+    assert_eq!(buffer.as_init_slice().len(), 6);
+
+    let vec = unsafe { buffer.into_vec::<u16>() }.unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+
+    let mut buffer = InitAllocation::new(Layout::new::<[u16; 2]>());
+    buffer.push_bytes(b"odd");
+    assert!(unsafe { buffer.into_vec::<u16>() }.is_err());
+}
+
+#[test]
+fn typed_allocation() {
+    use alloc::rc::Rc;
+    let value = Rc::new(5);
+    let mut typed = TypedAllocation::new();
+    assert!(!typed.is_written());
+    typed.write(value.clone());
+    typed.write(value.clone());
+    assert_eq!(Rc::strong_count(&value), 2);
+    let boxed: Box<Rc<i32>> = typed.assume_init();
+    assert_eq!(**boxed, 5);
+    drop(boxed);
+    assert_eq!(Rc::strong_count(&value), 1);
+
+    let mut typed = TypedAllocation::new();
+    typed.write(value.clone());
+    drop(typed);
+    assert_eq!(Rc::strong_count(&value), 1);
+
+    let tracking = TrackingAllocator::new(Global);
+    let typed = TypedAllocation::<Rc<i32>, _>::new_in(&tracking);
+    assert_eq!(tracking.live_allocations(), 1);
+    drop(typed);
+    assert_eq!(tracking.live_allocations(), 0);
+}
+
+#[test]
+#[should_panic = "value has not been written"]
+fn typed_allocation_unwritten() {
+    let _ = TypedAllocation::<u32>::new().assume_init();
+}
+
+#[test]
+fn as_chunks() {
+    let alloc = Allocation::from_slice(b"0123456789abcdefg");
+    let (chunks, remainder) = alloc.as_chunks::<4>();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(remainder.len(), 1);
+    assert_eq!(unsafe { chunks[1][2].assume_init() }, b'6');
+    assert_eq!(unsafe { remainder[0].assume_init() }, b'g');
+}
+
+#[test]
+fn boxed_slice_capacity() {
+    let boxed = alloc::vec![1, 2, 3].into_boxed_slice();
+    let alloc = Allocation::from(boxed);
+    assert_eq!(alloc.layout(), Layout::array::<i32>(3).unwrap());
+    let vec = alloc.try_into_vec::<i32>().unwrap();
+    assert_eq!(vec.capacity(), 3);
+}
+
+#[test]
+fn fn_allocator() {
+    use core::ffi::c_void;
+    // Stand-ins for C functions, storing the size in front of the returned memory
+    const HEADER: usize = 16;
+    fn header_layout(size: usize) -> Layout {
+        Layout::from_size_align(size + HEADER, HEADER).unwrap()
+    }
+    unsafe extern "C" fn test_alloc(size: usize) -> *mut c_void {
+        let ptr = unsafe { alloc::alloc::alloc(header_layout(size)) };
+        unsafe { ptr.cast::<usize>().write(size) };
+        unsafe { ptr.add(HEADER).cast() }
+    }
+    unsafe extern "C" fn test_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+        let ptr = unsafe { ptr.cast::<u8>().sub(HEADER) };
+        let old_size = unsafe { ptr.cast::<usize>().read() };
+        let ptr = unsafe { alloc::alloc::realloc(ptr, header_layout(old_size), size + HEADER) };
+        unsafe { ptr.cast::<usize>().write(size) };
+        unsafe { ptr.add(HEADER).cast() }
+    }
+    unsafe extern "C" fn test_free(ptr: *mut c_void) {
+        let ptr = unsafe { ptr.cast::<u8>().sub(HEADER) };
+        let size = unsafe { ptr.cast::<usize>().read() };
+        unsafe { alloc::alloc::dealloc(ptr, header_layout(size)) };
+    }
+
+    let fn_alloc = unsafe { FnAllocator::new(test_alloc, test_realloc, test_free, HEADER) };
+    let mut alloc = Allocation::from_value_in(*b"callback", fn_alloc);
+    alloc.realloc(Layout::new::<[u8; 32]>());
+    assert_eq!(unsafe { alloc.read_value::<[u8; 8]>() }, *b"callback");
+    alloc.realloc(Layout::new::<()>());
+    assert!(alloc.is_zero_sized());
+
+    assert!(Allocation::try_new_in(Layout::from_size_align(8, 32).unwrap(), fn_alloc).is_err());
+    // small blocks are only aligned for objects fitting into them
+    assert!(Allocation::try_new_in(Layout::from_size_align(1, 8).unwrap(), fn_alloc).is_err());
+}
+
+#[test]
+fn allocation_bytes() {
+    let mut bytes = AllocationBytes::from(Allocation::from_slice(b"stable"));
+    let addr = bytes.as_ptr();
+    bytes[0].write(b'S');
+    let moved = alloc::vec![bytes];
+    assert_eq!(moved[0].as_ptr(), addr);
+    assert_eq!(moved[0].len(), 6);
+
+    #[cfg(feature = "stable_deref")]
+    {
+        fn assert_stable<T: stable_deref_trait::StableDeref>(_: &T) {}
+        assert_stable(&moved[0]);
+    }
+}
+
+#[test]
+fn realloc_exact() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 64]>());
+    alloc.realloc(Layout::new::<[u8; 32]>());
+    assert_eq!(alloc.layout().size(), 64);
+
+    alloc.realloc_exact(Layout::new::<[u8; 32]>());
+    assert_eq!(alloc.layout().size(), 32);
+    alloc.realloc_exact(Layout::new::<[u8; 16]>());
+    assert_eq!(alloc.layout().size(), 16);
+    alloc.try_realloc_exact(Layout::new::<[u8; 8]>()).unwrap();
+    assert_eq!(alloc.layout(), alloc.requested_layout());
+    assert_eq!(alloc.layout().size(), 8);
+}
+
+#[test]
+fn hash_init_bytes() {
+    use core::hash::{Hash, Hasher};
+
+    /// FNV-1a, to hash without std.
+    struct Fnv(u64);
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+    fn hash(bytes: &(impl Hash + ?Sized)) -> u64 {
+        let mut hasher = Fnv(0xcbf29ce484222325);
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let counting = Counting::default();
+    let a = Allocation::from_slice(b"key");
+    let b = Allocation::from_value_in(*b"key", &counting);
+    let (a_bytes, b_bytes) = unsafe { (a.as_init_bytes(), b.as_init_bytes()) };
+    assert_eq!(hash(a_bytes), hash(b_bytes));
+    assert_ne!(hash(a_bytes), hash(b"kez"));
+
+    let borrowed: &[core::mem::MaybeUninit<u8>] = core::borrow::Borrow::borrow(&a);
+    assert_eq!(borrowed.len(), 3);
+
+    // views hash and compare like the byte slice they borrow as
+    let (a, b) = unsafe { (ByteView::new(a), ByteView::new(b)) };
+    assert!(a == b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(hash(&a), hash(&b"key"[..]));
+    let kez = unsafe { ByteView::new(Allocation::from_slice(b"kez")) };
+    assert!(a != kez);
+    assert_eq!(b.into_inner().layout().size(), 3);
+}
+
+#[test]
+fn realloc_in_place_or_copy() {
+    let mut alloc = Allocation::from_value([7u8; 64]);
+    alloc.realloc(Layout::new::<[u8; 16]>());
+    assert_eq!(
+        alloc.try_realloc_in_place_or_copy(Layout::new::<[u8; 64]>()),
+        Ok(Reallocation::InPlace)
+    );
+
+    // Changing the alignment allocates a new block before the old one is freed, so the address must change.
+    let align = 4096;
+    let layout = Layout::from_size_align(128, align).unwrap();
+    assert_eq!(
+        alloc.try_realloc_in_place_or_copy(layout),
+        Ok(Reallocation::Moved)
+    );
+    assert_eq!(alloc.as_ptr::<u8>().as_ptr() as usize % align, 0);
+    assert_eq!(unsafe { alloc.read_value::<[u8; 64]>() }, [7; 64]);
+}
+
+#[test]
+fn with_capacity_bytes() {
+    let alloc = Allocation::with_capacity_bytes(16);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 16]>());
+
+    let counting = Counting::default();
+    let mut buf = InitAllocation::from(Allocation::with_capacity_bytes_in(16, &counting));
+    for i in 0..1000u32 {
+        buf.push_bytes(&[i as u8]);
+    }
+    // One allocation, then doubling from 16 to 1024 bytes.
+    assert_eq!(counting.calls.get(), 1 + 6);
+    assert_eq!(buf.len(), 1000);
+    assert!(buf
+        .as_init_slice()
+        .iter()
+        .enumerate()
+        .all(|(i, &b)| b == i as u8));
+}
+
+#[test]
+fn resize_elements() {
+    let mut alloc = Allocation::from_value([1i32, 2]);
+    alloc.resize_elements_zeroed::<i32>(4);
+    assert_eq!(alloc.layout(), Layout::new::<[i32; 4]>());
+    assert_eq!(unsafe { alloc.read_value::<[i32; 4]>() }, [1, 2, 0, 0]);
+
+    alloc.resize_elements::<u8>(3);
+    assert_eq!(alloc.layout().align(), align_of::<i32>());
+    assert_eq!(alloc.requested_size(), 3);
+    assert_eq!(unsafe { alloc.read_value::<i32>() }, 1);
+}
+
+#[test]
+#[cfg_attr(miri, ignore = "volatile semantics are not fully modeled")]
+fn volatile_copy_from_slice() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 8]>());
+    alloc.volatile_copy_from_slice(b"mmio");
+    assert_eq!(unsafe { alloc.read_value::<[u8; 4]>() }, *b"mmio");
+}
+
+#[test]
+#[should_panic = "allocation does not fit 9 bytes"]
+fn volatile_copy_from_slice_too_large() {
+    let mut alloc = Allocation::new(Layout::new::<[u8; 8]>());
+    alloc.volatile_copy_from_slice(&[0; 9]);
+}
+
+#[test]
+fn reset_to() {
+    let tracking = TrackingAllocator::new(Global);
+    let mut alloc = Allocation::new_in(Layout::new::<[u8; 64]>(), &tracking);
+    #[cfg(feature = "generation")]
+    let generation = alloc.generation();
+    alloc.reset_to(Layout::new::<[u8; 16]>());
+    assert_eq!(tracking.live_allocations(), 1);
+    assert_eq!(tracking.bytes_allocated(), 16);
+    assert_eq!(alloc.layout(), Layout::new::<[u8; 16]>());
+    #[cfg(feature = "generation")]
+    assert_ne!(alloc.generation(), generation);
+
+    let back = Allocation::new_in(Layout::new::<[u8; 8]>(), &tracking);
+    let front = alloc.replace_with(back);
+    assert_eq!(front.layout().size(), 16);
+    assert_eq!(alloc.layout().size(), 8);
+    drop(front);
+    assert_eq!(tracking.bytes_allocated(), 8);
+}
+
+#[test]
+fn into_vec_lossy() {
+    let alloc = Allocation::new(Layout::from_size_align(17, 4).unwrap());
+    let (mut vec, discarded) = alloc.try_into_vec_lossy::<i32>().unwrap();
+    assert_eq!(vec.capacity(), 4);
+    assert_eq!(discarded, 1);
+    vec.extend(0..5);
+    drop(vec);
+
+    let exact = Allocation::new(Layout::new::<[u16; 3]>());
+    let (vec, discarded) = exact.try_into_vec_lossy::<u16>().unwrap();
+    assert_eq!((vec.capacity(), discarded), (3, 0));
+
+    let alloc = Allocation::new(Layout::from_size_align(17, 1).unwrap());
+    assert!(matches!(
+        alloc.try_into_vec_lossy::<i32>(),
+        Err(VecConversionError::AlignMismatch { .. })
+    ));
+}
+
+#[test]
+fn uninit_array_box() {
+    let alloc = Allocation::new(Layout::from_size_align(16, 4).unwrap());
+    let mut boxed = alloc.try_into_uninit_array_box::<u32, 4>().unwrap();
+    boxed.write([1, 2, 3, 4]);
+    let boxed = unsafe { boxed.assume_init() };
+    assert_eq!(*boxed, [1, 2, 3, 4]);
+
+    let alloc = Allocation::new(Layout::new::<[u8; 16]>());
+    assert!(alloc.try_into_uninit_array_box::<u32, 4>().is_err());
+}
+
+#[test]
+fn addr() {
+    let first = Allocation::new(Layout::new::<u64>());
+    let second = Allocation::new(Layout::new::<u64>());
+    assert_eq!(first.addr().get(), first.as_ptr::<u8>().as_ptr() as usize);
+    assert_ne!(first.addr(), second.addr());
+}
+
+#[test]
+fn map_in_place() {
+    let mut alloc = Allocation::from_slice(b"mask");
+    unsafe { alloc.map_in_place(|b| b ^ 0xff) };
+    assert_eq!(
+        unsafe { alloc.as_init_bytes() },
+        [!b'm', !b'a', !b's', !b'k']
+    );
+    unsafe { alloc.map_in_place(|b| b ^ 0xff) };
+    assert_eq!(unsafe { alloc.as_init_bytes() }, b"mask");
+}
+
+#[test]
+fn shrink_to() {
+    let mut alloc = Allocation::new(Layout::new::<[u32; 16]>());
+    alloc.realloc(Layout::new::<[u32; 2]>());
+    #[cfg(feature = "generation")]
+    let generation = alloc.generation();
+    alloc.shrink_to(128);
+    #[cfg(feature = "generation")]
+    assert_eq!(alloc.generation(), generation);
+    assert_eq!(alloc.layout().size(), 64);
+
+    alloc.shrink_to(32);
+    assert_eq!(alloc.layout(), Layout::new::<[u32; 8]>());
+    assert_eq!(alloc.requested_layout(), Layout::new::<[u32; 2]>());
+    // Never below the requested size
+    alloc.shrink_to(0);
+    assert_eq!(alloc.layout(), Layout::new::<[u32; 2]>());
+}
+
+#[test]
+fn vec_with_exact_capacity() {
+    let boxed: Box<[u32]> = alloc::vec![1, 2, 3].into_boxed_slice();
+    let alloc = Allocation::from(boxed);
+    let vec = alloc.try_into_vec_with_exact_capacity::<u32>(3).unwrap();
+    assert_eq!(vec.capacity(), 3);
+
+    let mut spare = alloc::vec::Vec::<u32>::with_capacity(8);
+    spare.push(1);
+    let alloc = Allocation::from(spare);
+    assert!(matches!(
+        alloc.try_into_vec_with_exact_capacity::<u32>(1),
+        Err(VecConversionError::SlackCapacity {
+            element_size: 4,
+            allocated: 32
+        })
+    ));
+}
+
+#[test]
+fn cache_aligned() {
+    let mut alloc = Allocation::new_in(Layout::new::<u8>(), CacheAligned(Global));
+    assert_eq!(alloc.addr().get() % 64, 0);
+    alloc.write_value(3u8);
+    alloc.realloc(Layout::new::<[u64; 32]>());
+    assert_eq!(alloc.addr().get() % 64, 0);
+    assert_eq!(unsafe { alloc.read_value::<u8>() }, 3);
+
+    let over_aligned = Layout::from_size_align(8, 256).unwrap();
+    let alloc = Allocation::new_in(over_aligned, CacheAligned(Global));
+    assert_eq!(alloc.addr().get() % 256, 0);
+}
+
+#[test]
+fn take_into_box() {
+    let mut alloc = Allocation::from_value(5u64);
+    assert!(alloc.take_into_box::<u32>().is_err());
+    assert_eq!(alloc.layout(), Layout::new::<u64>());
+
+    let boxed = alloc.take_into_box::<u64>().unwrap();
+    assert_eq!(unsafe { boxed.assume_init() }, Box::new(5));
+    assert_eq!(alloc.layout().size(), 0);
+
+    let mut alloc = Allocation::new(Layout::new::<[u16; 4]>());
+    let vec = alloc.take_into_vec::<u16>().unwrap();
+    assert_eq!(vec.capacity(), 4);
+    assert_eq!(alloc.layout().size(), 0);
+    alloc.realloc(Layout::new::<u16>());
+}
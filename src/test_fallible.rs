@@ -0,0 +1,44 @@
+//! Tests of the fallible API, which is also available with `--cfg no_global_oom_handling`.
+
+use core::alloc::Layout;
+
+use crate::alloc_shim::Global;
+use crate::*;
+
+#[test]
+fn try_new() {
+    let mut alloc = Allocation::try_new_in(Layout::new::<u32>(), Global).unwrap();
+    alloc.write_value(42u32);
+    alloc.try_realloc(Layout::new::<[u32; 4]>()).unwrap();
+    assert_eq!(unsafe { alloc.read_value::<u32>() }, 42);
+    alloc.try_shrink(Layout::new::<u32>()).unwrap();
+    assert_eq!(alloc.layout(), Layout::new::<u32>());
+
+    let alloc = Allocation::try_zeroed_in(Layout::new::<u64>(), Global).unwrap();
+    assert_eq!(unsafe { alloc.read_value::<u64>() }, 0);
+}
+
+#[test]
+fn try_reserve() {
+    let mut alloc = Allocation::try_from_slice_in(b"data", Global).unwrap();
+    alloc.try_reserve(4).unwrap();
+    assert!(alloc.len() >= 8);
+    alloc.try_shrink_to_fit().unwrap();
+    assert_eq!(alloc.len(), 8);
+    assert!(alloc.try_reserve(usize::MAX).is_err());
+}
+
+#[test]
+fn try_batch() {
+    let mut alloc = Allocation::try_new_in(Layout::new::<u8>(), Global).unwrap();
+    let mut batch = alloc.begin_batch();
+    batch.request(Layout::new::<[u16; 8]>()).unwrap();
+    batch.commit().unwrap();
+    assert!(alloc.fits::<[u16; 8]>());
+}
+
+#[test]
+fn try_typed() {
+    let mut typed = TypedAllocation::<u32, _>::try_new_in(Global).unwrap();
+    assert_eq!(*typed.write(3), 3);
+}
@@ -0,0 +1,118 @@
+use core::{alloc::Layout, marker::PhantomData, mem::ManuallyDrop};
+
+use alloc::boxed::Box;
+
+use crate::{
+    alloc_shim::{AllocError, Allocator, Global},
+    Allocation,
+};
+
+/// An allocation for a single `T` that tracks whether the value has been written.
+///
+/// This is a typed front-end to [`Allocation`] for the allocate-then-initialize pattern. A written value is dropped
+/// together with the allocation, an unwritten allocation is deallocated without touching its memory.
+pub struct TypedAllocation<T, A: Allocator = Global> {
+    inner: Allocation<A>,
+    written: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> TypedAllocation<T> {
+    /// Allocate new memory for a `T`.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T> Default for TypedAllocation<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Allocator> TypedAllocation<T, A> {
+    /// Allocate new memory for a `T` in a given allocator.
+    ///
+    /// # Panics
+    ///
+    /// This calls [`alloc::alloc::handle_alloc_error`] when no memory could be allocated, which can panic.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new_in(alloc: A) -> Self {
+        Self::from_allocation(Allocation::new_for_in::<T>(alloc))
+    }
+    /// Allocate new memory for a `T` in a given allocator.
+    ///
+    /// Same as [`Self::new_in`] but returns an error when memory could not be allocated.
+    pub fn try_new_in(alloc: A) -> Result<Self, AllocError> {
+        Allocation::try_new_in(Layout::new::<T>(), alloc).map(Self::from_allocation)
+    }
+    fn from_allocation(inner: Allocation<A>) -> Self {
+        Self {
+            inner,
+            written: false,
+            _marker: PhantomData,
+        }
+    }
+    /// Returns `true` if a value has been written.
+    pub fn is_written(&self) -> bool {
+        self.written
+    }
+    /// Move a value into the allocation, dropping a previously written value.
+    pub fn write(&mut self, value: T) -> &mut T {
+        if self.written {
+            // Forget about the old value first, in case dropping it panics
+            self.written = false;
+            unsafe { self.inner.as_ptr::<T>().drop_in_place() };
+        }
+        let value = self.inner.write_value(value);
+        self.written = true;
+        value
+    }
+    fn into_written(self) -> Allocation<A> {
+        assert!(self.written, "value has not been written");
+        let this = ManuallyDrop::new(self);
+        unsafe { core::ptr::read(&this.inner) }
+    }
+}
+
+#[cfg(feature = "nightly-std-conversions")]
+impl<T, A: Allocator> TypedAllocation<T, A> {
+    /// Convert into a box of the written value.
+    ///
+    /// # Panics
+    ///
+    /// If no value has been written.
+    pub fn assume_init(self) -> Box<T, A> {
+        // The requested layout fits the memory block, see `Allocation::into_parts_with_alloc`.
+        let (ptr, _, alloc) = self.into_written().into_parts_with_alloc();
+        unsafe { Box::from_raw_in(ptr.as_ptr().cast(), alloc) }
+    }
+}
+
+#[cfg(not(feature = "nightly-std-conversions"))]
+impl<T> TypedAllocation<T> {
+    /// Convert into a box of the written value.
+    ///
+    /// # Panics
+    ///
+    /// If no value has been written.
+    pub fn assume_init(self) -> Box<T> {
+        // The requested layout fits the memory block, see `Allocation::into_parts_with_alloc`.
+        let (ptr, _) = self.into_written().into_parts();
+        unsafe { Box::from_raw(ptr.as_ptr().cast()) }
+    }
+}
+
+impl<T, A: Allocator> Drop for TypedAllocation<T, A> {
+    fn drop(&mut self) {
+        if self.written {
+            unsafe { self.inner.as_ptr::<T>().drop_in_place() };
+        }
+    }
+}